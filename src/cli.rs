@@ -1,18 +1,55 @@
-use lemmy_help::{vimdoc::VimDoc, FromEmmy, Layout, LemmyHelp, Settings};
+use lemmy_help::{
+    ctags::Ctags, man::Man, meta::Meta, typescript::TypeScript, vimdoc::VimDoc, FromEmmy, Layout,
+    LemmyHelp, Nodes, Settings,
+};
 
 use lexopt::{
     Arg::{Long, Short, Value},
     Parser, ValueExt,
 };
-use std::{ffi::OsString, fs::read_to_string, path::PathBuf, str::FromStr};
+use std::{
+    ffi::OsString,
+    fs::{read_to_string, write},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 
 pub const NAME: &str = env!("CARGO_PKG_NAME");
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 pub const DESC: &str = env!("CARGO_PKG_DESCRIPTION");
 pub const AUTHOR: &str = env!("CARGO_PKG_AUTHORS");
 
+#[derive(Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Vimdoc,
+    Man,
+    Meta,
+    TypeScript,
+    Json,
+    Ctags,
+}
+
+impl FromStr for Format {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "vimdoc" => Ok(Self::Vimdoc),
+            "man" => Ok(Self::Man),
+            "meta" => Ok(Self::Meta),
+            "dts" => Ok(Self::TypeScript),
+            "json" => Ok(Self::Json),
+            "ctags" => Ok(Self::Ctags),
+            _ => Err(()),
+        }
+    }
+}
+
 pub struct Cli {
     modeline: bool,
+    tags: bool,
+    format: Format,
+    output: Option<PathBuf>,
     settings: Settings,
     files: Vec<PathBuf>,
 }
@@ -21,6 +58,9 @@ impl Default for Cli {
     fn default() -> Self {
         Self {
             modeline: true,
+            tags: false,
+            format: Format::default(),
+            output: None,
             settings: Settings::default(),
             files: vec![],
         }
@@ -55,15 +95,35 @@ impl Cli {
                             value: l.into(),
                         })?;
                 }
+                Short('F') | Long("format") => {
+                    let format = parser.value()?;
+                    let Some(f) = format.to_str() else {
+                        return Err(lexopt::Error::MissingValue {
+                            option: Some("format".into()),
+                        });
+                    };
+                    c.format = Format::from_str(f).map_err(|_| lexopt::Error::UnexpectedValue {
+                        option: "format".into(),
+                        value: f.into(),
+                    })?;
+                }
                 Short('i') | Long("indent") => {
                     c.settings.indent_width = parser.value()?.parse()?;
                 }
+                Short('w') | Long("width") => {
+                    c.settings.text_width = parser.value()?.parse()?;
+                }
+                Short('o') | Long("output") => c.output = Some(parser.value()?.into()),
                 Short('M') | Long("no-modeline") => c.modeline = false,
+                Short('T') | Long("tags") => c.tags = true,
                 Short('f') | Long("prefix-func") => c.settings.prefix_func = true,
                 Short('a') | Long("prefix-alias") => c.settings.prefix_alias = true,
                 Short('c') | Long("prefix-class") => c.settings.prefix_class = true,
                 Short('t') | Long("prefix-type") => c.settings.prefix_type = true,
                 Long("expand-opt") => c.settings.expand_opt = true,
+                Long("wrap") => c.settings.wrap = true,
+                Long("omit-deprecated") => c.settings.omit_deprecated = true,
+                Long("changelog") => c.settings.changelog = true,
                 Value(val) => {
                     let file = PathBuf::from(&val);
                     if !file.is_file() {
@@ -78,22 +138,93 @@ impl Cli {
             }
         }
 
+        if c.tags && (c.output.is_none() || c.format != Format::Vimdoc) {
+            return Err("--tags can only be used with --output and vimdoc format".into());
+        }
+
         Ok(c)
     }
 
-    pub fn run(self) {
+    pub fn run(self) -> Result<(), String> {
+        if self.format == Format::Ctags {
+            let mut ctags = Ctags::new();
+            for f in &self.files {
+                let source = read_to_string(f).unwrap();
+                ctags
+                    .parse(&source, &f.to_string_lossy())
+                    .map_err(|e| format!("{}: {e:?}", f.display()))?;
+            }
+            return self.write(ctags.to_string());
+        }
+
         let mut lemmy = LemmyHelp::new();
 
-        for f in self.files {
+        for f in &self.files {
             let source = read_to_string(f).unwrap();
             lemmy.for_help(&source, &self.settings).unwrap();
         }
 
-        print!("{}", VimDoc::from_emmy(&lemmy, &self.settings));
+        let doc = match self.format {
+            Format::Vimdoc => {
+                let vimdoc = VimDoc::from_emmy(&lemmy, &self.settings);
+
+                if let (true, Some(output)) = (self.tags, &self.output) {
+                    Self::write_tags(&vimdoc, output)?;
+                }
+
+                let mut doc = vimdoc.to_string();
+                if self.modeline {
+                    doc.push_str(&format!(
+                        "vim:tw={}:ts=8:noet:ft=help:norl:\n",
+                        self.settings.text_width
+                    ));
+                }
+                doc
+            }
+            Format::Man => Man::from_emmy(&lemmy, &self.settings).to_string(),
+            Format::Meta => Meta::from_emmy(&lemmy, &self.settings).to_string(),
+            Format::TypeScript => TypeScript::from_emmy(&lemmy, &self.settings).to_string(),
+            Format::Json => {
+                let mut doc = serde_json::to_string_pretty(lemmy.nodes()).unwrap();
+                doc.push('\n');
+                doc
+            }
+            Format::Ctags => unreachable!(),
+        };
+
+        self.write(doc)
+    }
+
+    /// Writes the document into the --output file, or to the stdout
+    fn write(&self, doc: String) -> Result<(), String> {
+        match &self.output {
+            Some(output) => write(output, doc).map_err(|e| format!("{}: {e}", output.display())),
+            None => {
+                print!("{doc}");
+                Ok(())
+            }
+        }
+    }
+
+    /// Writes the `tags` file, same as `:helptags`, next to the help file
+    fn write_tags(vimdoc: &VimDoc, output: &Path) -> Result<(), String> {
+        let help = output
+            .file_name()
+            .map(|f| f.to_string_lossy())
+            .unwrap_or_default();
 
-        if self.modeline {
-            println!("vim:tw=78:ts=8:noet:ft=help:norl:");
+        let tags = vimdoc
+            .tags()
+            .map_err(|tag| format!("Duplicate tag *{tag}* in {help}"))?;
+
+        let mut doc = String::new();
+        for tag in tags {
+            let pat = tag.replace('\\', "\\\\").replace('/', "\\/");
+            doc.push_str(&format!("{tag}\t{help}\t/*{pat}*\n"));
         }
+
+        let path = output.with_file_name("tags");
+        write(&path, doc).map_err(|e| format!("{}: {e}", path.display()))
     }
 
     #[inline]
@@ -112,15 +243,28 @@ ARGS:
 FLAGS:
     -h, --help                  Print help information
     -v, --version               Print version information
-    -M, --no-modeline           Don't print modeline at the end
+    -M, --no-modeline           Don't print modeline at the end (vimdoc)
+    -T, --tags                  Write a 'tags' file next to the --output (vimdoc)
     -f, --prefix-func           Prefix function name with ---@mod name
     -a, --prefix-alias          Prefix ---@alias tag with return/---@mod name
     -c, --prefix-class          Prefix ---@class tag with return/---@mod name
     -t, --prefix-type           Prefix ---@type tag with ---@mod name
         --expand-opt            Expand '?' (optional) to 'nil' type
+        --wrap                  Reflow descriptions to fit in the --width (vimdoc)
+        --omit-deprecated       Omit the '---@deprecated' items
+        --changelog             Append a changelog grouped by '---@since' (vimdoc)
 
 OPTIONS:
+    -F, --format <format>       Output format [default: 'vimdoc']
+                                - "vimdoc" : Vim help file
+                                - "man" : roff man page
+                                - "meta" : LuaLS ---@meta definition file
+                                - "dts" : TypeScript declaration file
+                                - "json" : Parsed nodes as JSON
+                                - "ctags" : Index of the documented symbols
+    -o, --output <file>         Write the output into <file> instead of stdout
     -i, --indent <u8>           Controls the indent width [default: 4]
+    -w, --width <usize>         Text width of the vimdoc [default: 80]
     -l, --layout <layout>       Vimdoc text layout [default: 'default']
                                 - "default" : Default layout
                                 - "compact[:n=0]" : Aligns [desc] with <type>
@@ -136,6 +280,10 @@ USAGE:
     {NAME} /path/to/first.lua /path/to/second.lua > doc/PLUGIN_NAME.txt
     {NAME} -c -a /path/to/{{first,second,third}}.lua > doc/PLUGIN_NAME.txt
     {NAME} --layout compact:2 /path/to/plugin.lua > doc/PLUGIN_NAME.txt
+    {NAME} --width 78 /path/to/plugin.lua > doc/PLUGIN_NAME.txt
+    {NAME} --format json /path/to/plugin.lua > nodes.json
+    {NAME} --tags -o doc/PLUGIN_NAME.txt /path/to/plugin.lua
+    {NAME} --format ctags lua/**/*.lua > tags
 
 NOTES:
     - The order of parsing + rendering is relative to the given files
@@ -0,0 +1,219 @@
+use std::{fmt::Display, ops::Range};
+
+use chumsky::prelude::Simple;
+
+use crate::{
+    lexer::{Name, TagType},
+    parser::{AliasKind, Class, Node},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Kind {
+    Function,
+    Class,
+    Alias,
+    Field,
+}
+
+impl Display for Kind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Function => f.write_str("function"),
+            Self::Class => f.write_str("class"),
+            Self::Alias => f.write_str("alias"),
+            Self::Field => f.write_str("field"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Entry {
+    pub name: String,
+    pub file: String,
+    /// 1-based line number of the symbol
+    pub line: usize,
+    pub kind: Kind,
+    /// Class which the field belongs to
+    pub scope: Option<String>,
+}
+
+impl Display for Entry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{};\"\tkind:{}\tline:{}",
+            self.name, self.file, self.line, self.kind, self.line
+        )?;
+        if let Some(scope) = &self.scope {
+            write!(f, "\tclass:{scope}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Universal-ctags compatible index of the documented symbols
+#[derive(Debug, Default)]
+pub struct Ctags {
+    entries: Vec<Entry>,
+}
+
+impl Ctags {
+    /// Creates a new index
+    ///
+    /// ```
+    /// use lemmy_help::ctags::Ctags;
+    ///
+    /// Ctags::new();
+    /// ```
+    pub fn new() -> Self {
+        Self { entries: vec![] }
+    }
+
+    /// Sorted entries of the index
+    pub fn entries(&self) -> Vec<&Entry> {
+        let mut entries = self.entries.iter().collect::<Vec<&Entry>>();
+        entries.sort();
+        entries
+    }
+
+    /// Parse the given lua source code and add its symbols into the index
+    ///
+    /// ```
+    /// use lemmy_help::ctags::Ctags;
+    ///
+    /// let src = r#"
+    /// local U = {}
+    ///
+    /// ---Add two integar and print it
+    /// ---@param this number First number
+    /// ---@param that number Second number
+    /// function U.sum(this, that)
+    ///     print(this + that)
+    /// end
+    ///
+    /// return U
+    /// "#;
+    ///
+    /// let mut ctags = Ctags::new();
+    /// ctags.parse(src, "lua/u.lua").unwrap();
+    ///
+    /// assert_eq!(ctags.entries()[0].name, "U.sum");
+    /// assert_eq!(ctags.entries()[0].line, 7);
+    /// ```
+    pub fn parse(&mut self, src: &str, file: &str) -> Result<&Self, Vec<Simple<TagType>>> {
+        let chars = src.chars().collect::<Vec<char>>();
+
+        for (node, span) in Node::spanned(src)? {
+            let mut push = |name: String, line: usize, kind: Kind, scope: Option<String>| {
+                self.entries.push(Entry {
+                    name,
+                    file: file.to_owned(),
+                    line,
+                    kind,
+                    scope,
+                })
+            };
+            match node {
+                Node::Func(x) => push(
+                    format!("{}{}", x.prefix.left.as_deref().unwrap_or_default(), x.op),
+                    code_line(&chars, &span),
+                    Kind::Function,
+                    None,
+                ),
+                Node::Type(x) => push(
+                    format!("{}{}", x.prefix.left.as_deref().unwrap_or_default(), x.op),
+                    code_line(&chars, &span),
+                    Kind::Field,
+                    None,
+                ),
+                Node::Alias(x) => push(
+                    x.name,
+                    tag_lines(
+                        &chars,
+                        &span,
+                        match x.kind {
+                            AliasKind::Table(..) => "---@enum",
+                            _ => "---@alias",
+                        },
+                    )
+                    .first()
+                    .copied()
+                    .unwrap_or(1),
+                    Kind::Alias,
+                    None,
+                ),
+                Node::Class(Class { name, fields, .. }) => {
+                    let lines = tag_lines(&chars, &span, "---@field");
+                    for (field, line) in fields.into_iter().zip(lines) {
+                        // Index signatures aren't symbols that can be jumped to
+                        if let Name::Req(n) | Name::Opt(n) = field.name {
+                            push(n, line, Kind::Field, Some(name.to_owned()));
+                        }
+                    }
+                    let line = tag_lines(&chars, &span, "---@class");
+                    push(name, line.first().copied().unwrap_or(1), Kind::Class, None);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(self)
+    }
+}
+
+impl Display for Ctags {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "!_TAG_FILE_FORMAT\t2\t/extended format; --format=1 will not append ;\" to lines/"
+        )?;
+        writeln!(
+            f,
+            "!_TAG_FILE_SORTED\t1\t/0=unsorted, 1=sorted, 2=foldcase/"
+        )?;
+        writeln!(f, "!_TAG_PROGRAM_NAME\t{}\t//", env!("CARGO_PKG_NAME"))?;
+        writeln!(
+            f,
+            "!_TAG_PROGRAM_VERSION\t{}\t//",
+            env!("CARGO_PKG_VERSION")
+        )?;
+        for entry in self.entries() {
+            writeln!(f, "{entry}")?;
+        }
+        Ok(())
+    }
+}
+
+// #################
+
+/// 1-based line number of the char at the given index
+#[inline]
+fn line_at(chars: &[char], idx: usize) -> usize {
+    chars[..idx].iter().filter(|c| **c == '\n').count() + 1
+}
+
+/// Line of the code i.e., last non-whitespace char, that comes after the emmylua
+#[inline]
+fn code_line(chars: &[char], span: &Range<usize>) -> usize {
+    let end = chars[span.start..span.end]
+        .iter()
+        .rposition(|c| !c.is_whitespace())
+        .map_or(span.start, |x| span.start + x);
+    line_at(chars, end)
+}
+
+/// Lines, inside the span, that starts with the given emmylua tag
+#[inline]
+fn tag_lines(chars: &[char], span: &Range<usize>, tag: &str) -> Vec<usize> {
+    let text = chars[span.start..span.end].iter().collect::<String>();
+    let first = line_at(chars, span.start);
+    text.lines()
+        .enumerate()
+        .filter(|(_, l)| {
+            l.trim_start()
+                .strip_prefix(tag)
+                .map_or(false, |rest| rest.starts_with(char::is_whitespace))
+        })
+        .map(|(i, _)| first + i)
+        .collect()
+}
@@ -0,0 +1,478 @@
+use std::{collections::HashMap, fmt::Display};
+
+use crate::{
+    lexer::{Generic, Name, Scope, Ty},
+    parser::{
+        Alias, AliasKind, Brief, Class, Divider, Field, Func, Module, Node, Param, Return, See,
+        Tag, Type, Usage,
+    },
+    Accept, FromEmmy, Nodes, Settings, Visitor,
+};
+
+/// File name (without extension) of the page generated from `---@toc`
+pub const INDEX: &str = "index";
+
+/// A single HTML document of the generated site
+#[derive(Debug)]
+pub struct Page {
+    /// File name of the page, without the `.html` extension
+    pub name: String,
+    pub title: String,
+    pub body: String,
+}
+
+impl Display for Page {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "<!DOCTYPE html>")?;
+        writeln!(f, "<html>")?;
+        writeln!(f, "<head>")?;
+        writeln!(f, "<meta charset=\"utf-8\">")?;
+        writeln!(f, "<title>{}</title>", escape(&self.title))?;
+        writeln!(f, "</head>")?;
+        writeln!(f, "<body>")?;
+        if self.name != INDEX {
+            writeln!(f, "<nav><a href=\"{INDEX}.html\">Index</a></nav>")?;
+        }
+        f.write_str(&self.body)?;
+        writeln!(f, "</body>")?;
+        writeln!(f, "</html>")
+    }
+}
+
+/// Static HTML site where every `---@mod` gets its own [`Page`]. Anything
+/// before the first `---@mod`, usually the `---@toc`, goes into the index page.
+///
+/// NOTE: [`Display`] only writes the index page, use [`Html::pages`] to get every page.
+#[derive(Debug, Default)]
+pub struct Html {
+    pages: Vec<Page>,
+    /// Tag name to the `href` of its anchor
+    tags: HashMap<String, String>,
+    /// `---@class` and `---@alias` names to the `href` of their anchor
+    types: HashMap<String, String>,
+}
+
+impl Html {
+    /// All the pages of the site, the first one being the index page
+    pub fn pages(&self) -> &[Page] {
+        &self.pages
+    }
+
+    fn link(&self, tag: &str) -> String {
+        match self.tags.get(tag) {
+            Some(href) => format!("<a href=\"{href}\"><code>{}</code></a>", escape(tag)),
+            None => format!("<code>{}</code>", escape(tag)),
+        }
+    }
+
+    /// Renders the type and hyperlinks every name that refers to a known class or alias
+    fn ty(&self, ty: &impl Display) -> String {
+        let ty = ty.to_string();
+        let mut doc = String::from("<code>");
+        let mut quoted = false;
+        let mut word = String::new();
+        for c in ty.chars().chain(Some('\0')) {
+            if !quoted && (c.is_alphanumeric() || C.contains(&c)) {
+                word.push(c);
+                continue;
+            }
+            if !word.is_empty() {
+                match self.types.get(&word) {
+                    Some(href) => doc.push_str(&format!("<a href=\"{href}\">{word}</a>")),
+                    None => doc.push_str(&escape(&word)),
+                }
+                word.clear();
+            }
+            if c == '"' {
+                quoted = !quoted;
+            }
+            if c != '\0' {
+                doc.push_str(&escape(&c.to_string()));
+            }
+        }
+        doc.push_str("</code>");
+        doc
+    }
+}
+
+impl Visitor for Html {
+    type R = String;
+    type S = Settings;
+
+    fn module(&self, n: &Module, _: &Self::S) -> Self::R {
+        format!(
+            "<h1 id=\"{}\">{}</h1>\n",
+            escape(&n.name),
+            escape(n.desc.as_deref().unwrap_or(&n.name))
+        )
+    }
+
+    fn divider(&self, _: &Divider, _: &Self::S) -> Self::R {
+        "<hr>\n".into()
+    }
+
+    fn brief(&self, n: &Brief, _: &Self::S) -> Self::R {
+        format!("<pre>{}</pre>\n", escape(&n.desc.join("\n")))
+    }
+
+    fn tag(&self, n: &Tag, _: &Self::S) -> Self::R {
+        format!("<span id=\"{}\"></span>\n", escape(&n.0))
+    }
+
+    fn func(&self, n: &Func, s: &Self::S) -> Self::R {
+        let args = n
+            .params
+            .iter()
+            .map(|p| p.name.to_string())
+            .collect::<Vec<String>>()
+            .join(", ");
+        let mut doc = header(
+            &format!(
+                "{}{}{}({args})",
+                n.prefix.left.as_deref().unwrap_or_default(),
+                n.op,
+                Generic::angled(&n.generics)
+            ),
+            &format!("{}{}", n.prefix.right.as_deref().unwrap_or_default(), n.op),
+        );
+        if !n.desc.is_empty() {
+            doc.push_str(&description(&n.desc));
+        }
+        if !n.params.is_empty() {
+            doc.push_str("<h4>Parameters</h4>\n");
+            doc.push_str(&self.params(&n.params, s));
+        }
+        if !n.returns.is_empty() {
+            doc.push_str("<h4>Returns</h4>\n");
+            doc.push_str(&self.returns(&n.returns, s));
+        }
+        if !n.see.refs.is_empty() {
+            doc.push_str(&self.see(&n.see, s));
+        }
+        if let Some(usage) = &n.usage {
+            doc.push_str(&self.usage(usage, s));
+        }
+        doc.push_str("</section>\n");
+        doc
+    }
+
+    fn params(&self, n: &[Param], s: &Self::S) -> Self::R {
+        let mut table = Table::default();
+        for param in n {
+            let (name, ty) = match (s.expand_opt, &param.name) {
+                (true, Name::Opt(n)) => (
+                    n.to_string(),
+                    self.ty(&Ty::union([Ty::Nil, param.ty.expand_opt()])),
+                ),
+                (true, n) => (n.to_string(), self.ty(&param.ty.expand_opt())),
+                (_, n) => (n.to_string(), self.ty(&param.ty)),
+            };
+            table.add_row([
+                format!("<code>{}</code>", escape(&name)),
+                ty,
+                escape(&param.desc.join("\n")),
+            ]);
+        }
+        table.to_string()
+    }
+
+    fn r#returns(&self, n: &[Return], _: &Self::S) -> Self::R {
+        let mut table = Table::default();
+        for entry in n {
+            table.add_row([
+                self.ty(&entry.ty),
+                escape(&if entry.desc.is_empty() {
+                    entry.name.clone().unwrap_or_default()
+                } else {
+                    entry.desc.join("\n")
+                }),
+            ]);
+        }
+        table.to_string()
+    }
+
+    fn class(&self, n: &Class, s: &Self::S) -> Self::R {
+        let name = format!("{}{}", n.name, Generic::angled(&n.generics));
+        let mut doc = match &n.prefix.right {
+            Some(prefix) => header(&name, &format!("{prefix}.{}", n.name)),
+            None => header(&name, &n.name),
+        };
+        if !n.parents.is_empty() {
+            let parents = n
+                .parents
+                .iter()
+                .map(|p| self.ty(p))
+                .collect::<Vec<String>>()
+                .join(", ");
+            doc.push_str(&format!("<p>Extends {parents}</p>\n"));
+        }
+        if !n.desc.is_empty() {
+            doc.push_str(&description(&n.desc));
+        }
+        if !n.fields.is_empty() {
+            doc.push_str("<h4>Fields</h4>\n");
+            doc.push_str(&self.fields(&n.fields, s));
+        }
+        if !n.see.refs.is_empty() {
+            doc.push_str(&self.see(&n.see, s));
+        }
+        doc.push_str("</section>\n");
+        doc
+    }
+
+    fn fields(&self, n: &[Field], s: &Self::S) -> Self::R {
+        let mut table = Table::default();
+        for field in n {
+            if field.scope != Scope::Public {
+                continue;
+            }
+            let (name, ty) = match (s.expand_opt, &field.name) {
+                (true, Name::Opt(n)) => (
+                    n.to_string(),
+                    self.ty(&Ty::union([Ty::Nil, field.ty.expand_opt()])),
+                ),
+                (true, n) => (n.to_string(), self.ty(&field.ty.expand_opt())),
+                (_, n) => (n.to_string(), self.ty(&field.ty)),
+            };
+            table.add_row([
+                format!("<code>{}</code>", escape(&name)),
+                ty,
+                escape(&field.desc.join("\n")),
+            ]);
+        }
+        table.to_string()
+    }
+
+    fn alias(&self, n: &Alias, _: &Self::S) -> Self::R {
+        let mut doc = match &n.prefix.right {
+            Some(prefix) => header(&n.name, &format!("{prefix}.{}", n.name)),
+            None => header(&n.name, &n.name),
+        };
+        if !n.desc.is_empty() {
+            doc.push_str(&description(&n.desc));
+        }
+        match &n.kind {
+            AliasKind::Type(ty) => {
+                doc.push_str("<h4>Type</h4>\n");
+                doc.push_str(&format!("<p>{}</p>\n", self.ty(ty)));
+            }
+            AliasKind::Enum(variants) => {
+                doc.push_str("<h4>Variants</h4>\n");
+                let mut table = Table::default();
+                for (ty, desc) in variants {
+                    table.add_row([self.ty(ty), escape(desc.as_deref().unwrap_or_default())]);
+                }
+                doc.push_str(&table.to_string());
+            }
+            AliasKind::Table(_, fields) => {
+                doc.push_str("<h4>Variants</h4>\n");
+                let mut table = Table::default();
+                for (key, value, desc) in fields {
+                    table.add_row([
+                        format!("<code>{}</code>", escape(key)),
+                        format!("<code>{}</code>", escape(value)),
+                        escape(&desc.join("\n")),
+                    ]);
+                }
+                doc.push_str(&table.to_string());
+            }
+        }
+        doc.push_str("</section>\n");
+        doc
+    }
+
+    fn r#type(&self, n: &Type, s: &Self::S) -> Self::R {
+        let mut doc = header(
+            &format!("{}{}", n.prefix.left.as_deref().unwrap_or_default(), n.op),
+            &format!("{}{}", n.prefix.right.as_deref().unwrap_or_default(), n.op),
+        );
+        let (extract, desc) = &n.desc;
+        if !extract.is_empty() {
+            doc.push_str(&description(extract));
+        }
+        doc.push_str("<h4>Type</h4>\n");
+        let mut table = Table::default();
+        table.add_row([self.ty(&n.ty), escape(desc.as_deref().unwrap_or_default())]);
+        doc.push_str(&table.to_string());
+        if !n.see.refs.is_empty() {
+            doc.push_str(&self.see(&n.see, s));
+        }
+        if let Some(usage) = &n.usage {
+            doc.push_str(&self.usage(usage, s));
+        }
+        doc.push_str("</section>\n");
+        doc
+    }
+
+    fn see(&self, n: &See, _: &Self::S) -> Self::R {
+        let mut doc = String::from("<h4>See</h4>\n<ul>\n");
+        for reff in &n.refs {
+            doc.push_str(&format!("<li>{}</li>\n", self.link(reff)));
+        }
+        doc.push_str("</ul>\n");
+        doc
+    }
+
+    fn usage(&self, n: &Usage, _: &Self::S) -> Self::R {
+        format!(
+            "<h4>Usage</h4>\n<pre><code class=\"language-{}\">{}</code></pre>\n",
+            escape(n.lang.as_deref().unwrap_or("lua")),
+            escape(&n.code)
+        )
+    }
+
+    fn toc(&self, n: &str, nodes: &[Node], s: &Self::S) -> Self::R {
+        let mut doc = self.module(
+            &Module {
+                name: n.to_string(),
+                desc: Some("Table of Contents".into()),
+                deprecated: None,
+                admonitions: vec![],
+            },
+            s,
+        );
+        doc.push_str("<ul>\n");
+        for nod in nodes {
+            if let Node::Module(x) = nod {
+                doc.push_str(&format!(
+                    "<li><a href=\"{}.html\">{}</a></li>\n",
+                    escape(&x.name),
+                    escape(x.desc.as_deref().unwrap_or(&x.name))
+                ));
+            }
+        }
+        doc.push_str("</ul>\n");
+        doc
+    }
+}
+
+impl FromEmmy for Html {
+    type Settings = Settings;
+    fn from_emmy(t: &impl Nodes, s: &Self::Settings) -> Self {
+        let mut shelf = Self::default();
+        let nodes = t.nodes();
+
+        // First pass: collect every anchor so that the links can point to the other pages
+        let mut page = INDEX;
+        for node in nodes {
+            let (tag, ty) = match node {
+                Node::Module(x) => {
+                    page = &x.name;
+                    (x.name.to_owned(), None)
+                }
+                Node::Tag(x) => (x.0.to_owned(), None),
+                Node::Func(x) => (
+                    format!("{}{}", x.prefix.right.as_deref().unwrap_or_default(), x.op),
+                    None,
+                ),
+                Node::Type(x) => (
+                    format!("{}{}", x.prefix.right.as_deref().unwrap_or_default(), x.op),
+                    None,
+                ),
+                Node::Class(x) => match &x.prefix.right {
+                    Some(prefix) => (format!("{prefix}.{}", x.name), Some(&x.name)),
+                    None => (x.name.to_owned(), Some(&x.name)),
+                },
+                Node::Alias(x) => match &x.prefix.right {
+                    Some(prefix) => (format!("{prefix}.{}", x.name), Some(&x.name)),
+                    None => (x.name.to_owned(), Some(&x.name)),
+                },
+                _ => continue,
+            };
+            let href = escape(&format!("{page}.html#{tag}"));
+            if let Some(ty) = ty {
+                shelf.types.insert(ty.to_owned(), href.clone());
+            }
+            shelf.tags.insert(tag, href);
+        }
+
+        // Second pass: render the nodes into their pages
+        let mut pages = vec![Page {
+            name: INDEX.into(),
+            title: INDEX.into(),
+            body: String::new(),
+        }];
+        for node in nodes {
+            if let Node::Module(x) = node {
+                pages.push(Page {
+                    name: x.name.to_owned(),
+                    title: x.desc.to_owned().unwrap_or_else(|| x.name.to_owned()),
+                    body: String::new(),
+                });
+            }
+            let doc = match node {
+                Node::Toc(x) => shelf.toc(x, nodes, s),
+                Node::Export(..) => continue,
+                _ => node.accept(&shelf, s),
+            };
+            if let Some(page) = pages.last_mut() {
+                page.body.push_str(&doc);
+            }
+        }
+        shelf.pages = pages;
+        shelf
+    }
+}
+
+impl Display for Html {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.pages.first() {
+            Some(index) => index.fmt(f),
+            None => Ok(()),
+        }
+    }
+}
+
+// #################
+
+/// Characters, other than alphanumeric, which can be part of a type name
+const C: [char; 3] = ['.', '_', '-'];
+
+#[derive(Default)]
+struct Table(Vec<Vec<String>>);
+
+impl Table {
+    pub fn add_row<T: Into<Vec<String>>>(&mut self, row: T) -> &Self {
+        self.0.push(row.into());
+        self
+    }
+}
+
+impl std::fmt::Display for Table {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "<table>")?;
+        for row in &self.0 {
+            f.write_str("<tr>")?;
+            for cell in row {
+                write!(f, "<td>{}</td>", cell.trim_end().replace('\n', "<br>"))?;
+            }
+            writeln!(f, "</tr>")?;
+        }
+        writeln!(f, "</table>")
+    }
+}
+
+#[inline]
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[inline]
+fn description(desc: &[String]) -> String {
+    format!(
+        "<p>{}</p>\n",
+        escape(&desc.join("\n")).replace('\n', "<br>\n")
+    )
+}
+
+#[inline]
+fn header(name: &str, tag: &str) -> String {
+    format!(
+        "<section id=\"{}\">\n<h3><code>{}</code></h3>\n",
+        escape(tag),
+        escape(name)
+    )
+}
@@ -4,7 +4,7 @@ pub use token::*;
 use std::ops::Range;
 
 use chumsky::{
-    prelude::{any, choice, end, filter, just, take_until, Simple},
+    prelude::{any, choice, end, filter, just, one_of, take_until, Simple},
     recursive::recursive,
     text::{ident, keyword, newline, whitespace, TextParser},
     Parser,
@@ -79,8 +79,25 @@ impl Lexer {
 
         let name = filter(|x: &char| x.is_alphanumeric() || C.contains(x))
             .repeated()
+            .at_least(1)
             .collect();
 
+        // Bracketed keys can be a type or a literal with double quotes i.e., `["key"]`
+        #[inline]
+        fn index_key(
+            ty: impl Parser<char, Ty, Error = Simple<char>> + Clone,
+        ) -> impl Parser<char, Name, Error = Simple<char>> + Clone {
+            just('"')
+                .ignore_then(filter(|c| c != &'"').repeated())
+                .then_ignore(just('"'))
+                .collect()
+                .map(|lit| Ty::Member(Member::Literal(lit)))
+                .or(ty)
+                .padded()
+                .delimited_by(just('['), just(']'))
+                .map(Name::Index)
+        }
+
         let ty = recursive(|inner| {
             let comma = just(',').padded();
             let colon = just(':').padded();
@@ -97,31 +114,36 @@ impl Lexer {
             let userdata = just("userdata").to(Ty::Userdata);
             let lightuserdata = just("lightuserdata").to(Ty::Lightuserdata);
 
+            // Array `T[]` and optional `T?` suffixes, in any order
             #[inline]
-            fn array_union(
+            fn suffixed(
                 p: impl Parser<char, Ty, Error = Simple<char>>,
-                inner: impl Parser<char, Ty, Error = Simple<char>>,
             ) -> impl Parser<char, Ty, Error = Simple<char>> {
-                p.then(just("[]").repeated())
-                    .foldl(|arr, _| Ty::Array(Box::new(arr)))
-                    // NOTE: Not the way I wanted i.e., Ty::Union(Vec<Ty>) it to be, but it's better than nothing
-                    .then(just('|').padded().ignore_then(inner).repeated())
-                    .foldl(|x, y| Ty::Union(Box::new(x), Box::new(y)))
+                p.then(
+                    just("[]")
+                        .to(Ty::Array as fn(_) -> _)
+                        .or(just('?').to(Ty::Opt as fn(_) -> _))
+                        .repeated(),
+                )
+                .foldl(|ty, suffix| suffix(Box::new(ty)))
             }
 
-            let list_like = ident()
-                .padded()
-                .then(optional)
-                .then(
-                    colon
-                        .ignore_then(inner.clone())
-                        .or_not()
-                        // NOTE: if param type is missing then LLS treats it as `any`
-                        .map(|x| x.unwrap_or(Ty::Any)),
-                )
-                .map(|((n, attr), t)| (attr(n), t))
-                .separated_by(comma)
-                .allow_trailing();
+            let index = index_key(inner.clone());
+
+            let list_like = choice((
+                index.padded(),
+                just("...").padded().map(|n| Name::Req(n.to_string())),
+                ident().padded().then(optional).map(|(n, attr)| attr(n)),
+            ))
+            .then(
+                colon
+                    .ignore_then(inner.clone())
+                    .or_not()
+                    // NOTE: if param type is missing then LLS treats it as `any`
+                    .map(|x| x.unwrap_or(Ty::Any)),
+            )
+            .separated_by(comma)
+            .allow_trailing();
 
             let fun = just("fun")
                 .ignore_then(
@@ -131,9 +153,39 @@ impl Lexer {
                 )
                 .then(
                     colon
-                        .ignore_then(inner.clone().separated_by(comma))
+                        .ignore_then(
+                            choice((
+                                // Parenthesized returns, optionally named i.e., (ok: boolean)
+                                // NOTE: a single unnamed return is just a type within parens
+                                ident()
+                                    .padded()
+                                    .then_ignore(colon)
+                                    .or_not()
+                                    .then(inner.clone())
+                                    .separated_by(comma)
+                                    .at_least(1)
+                                    .delimited_by(just('('), just(')'))
+                                    .try_map(|ret: Vec<(Option<String>, Ty)>, span| {
+                                        match ret.as_slice() {
+                                            [(None, _)] => Err(Simple::custom(span, "not a list")),
+                                            _ => Ok(ret),
+                                        }
+                                    }),
+                                inner
+                                    .clone()
+                                    .map(|ty| (None, ty))
+                                    .separated_by(comma)
+                                    .at_least(1),
+                            ))
+                            .or_not(),
+                        )
                         .or_not(),
                 )
+                // NOTE: a colon without any return type i.e., `fun():` or `fun(): ()` is invalid
+                .try_map(|(param, ret), span| match ret {
+                    Some(None) => Err(Simple::custom(span, "missing return type")),
+                    ret => Ok((param, ret.flatten())),
+                })
                 .map(|(param, ret)| Ty::Fun(param, ret));
 
             let table = just("table")
@@ -151,7 +203,44 @@ impl Lexer {
                 .delimited_by(just('{').then(whitespace()), whitespace().then(just('}')))
                 .map(Ty::Dict);
 
-            let ty_name = name.map(Ty::Ref);
+            let tuple = inner
+                .clone()
+                .separated_by(comma)
+                .at_least(1)
+                .allow_trailing()
+                .delimited_by(just('[').then(whitespace()), whitespace().then(just(']')))
+                .map(Ty::Tuple);
+
+            let digits = filter(char::is_ascii_digit).repeated().at_least(1);
+            let number = just('-')
+                .or_not()
+                .chain::<char, _, _>(digits)
+                .chain::<char, _, _>(just('.').chain(digits).or_not().flatten())
+                .collect()
+                .map(Ty::Num);
+
+            let bool_literal = keyword("true")
+                .to(Ty::Bool(true))
+                .or(keyword("false").to(Ty::Bool(false)));
+
+            // NOTE: `fun` is reserved, otherwise an invalid function would be a reference
+            let ty_name = name
+                .try_map(|name: String, span| match name.as_str() {
+                    "fun" => Err(Simple::custom(span, "invalid function type")),
+                    _ => Ok(name),
+                })
+                .then(
+                    inner
+                        .clone()
+                        .separated_by(comma)
+                        .at_least(1)
+                        .delimited_by(just('<').then(whitespace()), just('>'))
+                        .or_not(),
+                )
+                .map(|(name, args)| match args {
+                    Some(args) => Ty::Apply(name, args),
+                    None => Ty::Ref(name),
+                });
 
             let parens = inner
                 .clone()
@@ -161,30 +250,135 @@ impl Lexer {
             let string_literal = union_literal.map(Ty::Member);
 
             choice((
-                array_union(any, inner.clone()),
-                array_union(unknown, inner.clone()),
-                array_union(nil, inner.clone()),
-                array_union(boolean, inner.clone()),
-                array_union(string, inner.clone()),
-                array_union(num, inner.clone()),
-                array_union(int, inner.clone()),
-                array_union(function, inner.clone()),
-                array_union(thread, inner.clone()),
-                array_union(userdata, inner.clone()),
-                array_union(lightuserdata, inner.clone()),
-                array_union(fun, inner.clone()),
-                array_union(table, inner.clone()),
-                array_union(dict, inner.clone()),
-                array_union(parens, inner.clone()),
-                array_union(string_literal, inner.clone()),
-                array_union(ty_name, inner),
+                suffixed(any),
+                suffixed(unknown),
+                suffixed(nil),
+                suffixed(boolean),
+                suffixed(string),
+                suffixed(num),
+                suffixed(int),
+                suffixed(function),
+                suffixed(thread),
+                suffixed(userdata),
+                suffixed(lightuserdata),
+                suffixed(fun),
+                suffixed(table),
+                suffixed(dict),
+                suffixed(parens),
+                suffixed(string_literal),
+                suffixed(tuple),
+                suffixed(number),
+                suffixed(bool_literal),
+                suffixed(ty_name),
             ))
+            .separated_by(just('|').padded())
+            .at_least(1)
+            .map(Ty::union)
         });
 
         let code_lang = ident().then_ignore(space).or_not();
 
+        let generic = ident()
+            .then(just(':').padded().ignore_then(ty.clone()).or_not())
+            .map(|(name, parent)| Generic { name, parent });
+
+        // Quoted lua string i.e., `"value"` or `'value'`, along with the quotes
+        let quoted = |q: char| {
+            just(q)
+                .chain(filter(move |c| *c != q).repeated())
+                .chain(just(q))
+                .collect::<String>()
+        };
+
+        // Anything within the brackets as long as those are balanced i.e., `rgb(255, 0, 0)`
+        let balanced = recursive(|balanced| {
+            let group = |open, close| {
+                balanced
+                    .clone()
+                    .delimited_by(just(open), just(close))
+                    .map(move |x: String| format!("{open}{x}{close}"))
+            };
+            choice((
+                quoted('"'),
+                quoted('\''),
+                group('(', ')'),
+                group('{', '}'),
+                group('[', ']'),
+                filter(|c: &char| !"\"'(){}[]".contains(*c)).map(String::from),
+            ))
+            .repeated()
+            .map(|parts: Vec<String>| parts.concat())
+        });
+        let group = |open, close| {
+            balanced
+                .clone()
+                .delimited_by(just(open), just(close))
+                .map(move |x: String| format!("{open}{x}{close}"))
+        };
+
+        // Key of the enum field, either a name or within brackets i.e., `["a-b"]` or `[2]`
+        let field_key = ident().or(group('[', ']'));
+
+        // Value of the enum field, which ends at a `,`, `;` or the trailing comment
+        let field_value = choice((
+            quoted('"'),
+            quoted('\''),
+            group('(', ')'),
+            group('{', '}'),
+            group('[', ']'),
+            // A single `-` as the `--` starts the trailing comment
+            just('-')
+                .then_ignore(filter(|c: &char| *c != '-').rewind())
+                .map(String::from),
+            filter(|c: &char| !"\"'(){}[],;\n-".contains(*c)).map(String::from),
+        ))
+        .repeated()
+        .at_least(1)
+        .map(|parts: Vec<String>| parts.concat().trim_end().to_owned());
+
+        // Plain lua comments, which are ignored inside the enum table
+        let lua_comment = just("--[[")
+            .then(take_until(just("]]")))
+            .ignored()
+            .or(just("--").then(take_until(newline())).ignored());
+
+        // Lines before a field, where only the `---` comments are its description
+        let field_comments = choice((triple.ignore_then(comment).map(Some), lua_comment.to(None)))
+            .padded()
+            .repeated()
+            .map(|lines| lines.into_iter().flatten().collect::<Vec<String>>());
+
+        let enum_field = field_comments
+            .clone()
+            .then(field_key)
+            .then_ignore(just('=').padded())
+            .then(field_value)
+            .then_ignore(one_of(",;").or_not())
+            .then(
+                just(' ')
+                    .repeated()
+                    .ignore_then(just("--"))
+                    .ignore_then(comment)
+                    .or_not(),
+            )
+            .map(|(((mut desc, key), value), trailing)| {
+                desc.extend(trailing.map(|c| c.trim_start_matches('-').trim().to_owned()));
+                (key, value, desc)
+            });
+
+        let enum_table = keyword("local")
+            .then(space)
+            .or_not()
+            .ignore_then(name)
+            .then_ignore(just('=').padded())
+            .then_ignore(just('{'))
+            .ignore_then(enum_field.padded().repeated().at_least(1))
+            .then_ignore(field_comments)
+            .then_ignore(just('}'));
+
         let tag = just('@').ignore_then(choice((
             hidden.or(public.clone().ignored()).to(TagType::Skip),
+            just("meta").to(TagType::Skip),
             just("toc")
                 .ignore_then(space)
                 .ignore_then(comment)
@@ -223,27 +417,83 @@ impl Lexer {
                     ))),
                 )))
                 .map(|(ty, (name, desc))| TagType::Return(ty, name, desc)),
+            keyword("async").to(TagType::Attribute(Attribute::Async)),
+            keyword("nodiscard").to(TagType::Attribute(Attribute::Nodiscard)),
+            just("overload")
+                .ignore_then(space)
+                .ignore_then(ty.clone())
+                .map(TagType::Overload),
+            just("generic")
+                .ignore_then(space)
+                .ignore_then(generic.separated_by(just(',').padded()).at_least(1))
+                .map(TagType::Generic),
             just("class")
                 .ignore_then(space)
-                .ignore_then(name)
-                .then(just(':').padded().ignore_then(ident()).or_not())
-                .map(|(name, parent)| TagType::Class(name, parent)),
+                .ignore_then(
+                    ident()
+                        .separated_by(just(',').padded())
+                        .delimited_by(just('(').padded(), just(')'))
+                        .then_ignore(space)
+                        .or_not()
+                        .map(Option::unwrap_or_default),
+                )
+                .then(name)
+                .then(
+                    ident()
+                        .map(|name| Generic { name, parent: None })
+                        .separated_by(just(',').padded())
+                        .at_least(1)
+                        .delimited_by(just('<').then(whitespace()), just('>'))
+                        .or_not()
+                        .map(Option::unwrap_or_default),
+                )
+                .then(
+                    just(':')
+                        .padded()
+                        .ignore_then(ty.clone().separated_by(just(',').padded()).at_least(1))
+                        .or_not()
+                        .map(Option::unwrap_or_default),
+                )
+                .map(|(((attributes, name), generics), parents)| {
+                    TagType::Class(attributes, name, generics, parents)
+                }),
             just("field")
                 .ignore_then(space.ignore_then(private.or(public)).or_not())
                 .then_ignore(space)
-                .then(ident())
-                .then(optional)
+                .then(choice((
+                    index_key(ty.clone()),
+                    ident().then(optional).map(|(n, o)| o(n)),
+                )))
                 .then_ignore(space)
                 .then(ty.clone())
                 .then(desc)
-                .map(|((((scope, name), opt), ty), desc)| {
-                    TagType::Field(scope.unwrap_or(Scope::Public), opt(name), ty, desc)
+                .map(|(((scope, name), ty), desc)| {
+                    TagType::Field(scope.unwrap_or(Scope::Public), name, ty, desc)
                 }),
+            just("operator")
+                .ignore_then(space)
+                .ignore_then(ident())
+                .then(
+                    ty.clone()
+                        .padded()
+                        .delimited_by(just('('), just(')'))
+                        .or_not(),
+                )
+                .then_ignore(just(':').padded())
+                .then(ty.clone())
+                .map(|((kind, operand), result)| TagType::Operator(kind, operand, result)),
             just("alias")
                 .ignore_then(space)
                 .ignore_then(name)
                 .then(space.ignore_then(ty.clone()).or_not())
                 .map(|(name, ty)| TagType::Alias(name, ty)),
+            just("enum")
+                .ignore_then(space)
+                .ignore_then(just("(key)").then(space).or_not().map(|k| k.is_some()))
+                .then(name)
+                // NOTE: without a table, or an empty one, it's just a comment
+                .then(whitespace().ignore_then(enum_table))
+                .map(|((key, name), fields)| TagType::Enum(key, name, fields)),
             just("type")
                 .ignore_then(space)
                 .ignore_then(ty)
@@ -257,6 +507,31 @@ impl Lexer {
                 .ignore_then(space)
                 .ignore_then(comment)
                 .map(TagType::See),
+            just("deprecated")
+                .ignore_then(desc)
+                .map(TagType::Deprecated),
+            just("since")
+                .ignore_then(space)
+                .ignore_then(comment)
+                .map(TagType::Since)
+                .or(just("version")
+                    .ignore_then(space)
+                    .ignore_then(comment)
+                    .map(TagType::Version)),
+            keyword("note")
+                .to(AdmonitionKind::Note)
+                .or(keyword("warning").to(AdmonitionKind::Warning))
+                .then_ignore(space)
+                .then(choice((
+                    just("[[").to(None),
+                    just("]]").to(Some(None)),
+                    comment.map(|x: String| Some(Some(x))),
+                )))
+                .map(|(kind, body)| match body {
+                    None => TagType::AdmonitionStart(kind),
+                    Some(None) => TagType::AdmonitionEnd,
+                    Some(Some(x)) => TagType::Admonition(kind, x),
+                }),
             just("usage").ignore_then(space).ignore_then(choice((
                 code_lang
                     .then(
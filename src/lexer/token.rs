@@ -1,6 +1,7 @@
 use std::fmt::Display;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Member {
     Literal(String),
     Ident(String),
@@ -65,14 +66,32 @@ pub enum TagType {
     /// ```
     Return(Ty, Option<String>, Option<String>),
     /// ```lua
-    /// ---@class <name>[: <parent>]
+    /// ---@overload fun(<name>: <type>[, ...])[: <type>]
     /// ```
-    Class(String, Option<String>),
+    Overload(Ty),
+    /// ```lua
+    /// ---@async
+    ///
+    /// ---@nodiscard
+    /// ```
+    Attribute(Attribute),
+    /// ```lua
+    /// ---@generic <name>[: <parent>][, <name>[: <parent>]...]
+    /// ```
+    Generic(Vec<Generic>),
+    /// ```lua
+    /// ---@class [(<attribute>[, attribute...])] <name>[<generic[, generic...]>][: <parent>[, parent...]]
+    /// ```
+    Class(Vec<String>, String, Vec<Generic>, Vec<Ty>),
     /// ```lua
     /// ---@field [public|private|protected] <name[?]> <type> [description]
     /// ```
     Field(Scope, Name, Ty, Option<String>),
     /// ```lua
+    /// ---@operator <kind>[(<operand>)]: <result>
+    /// ```
+    Operator(String, Option<Ty>, Ty),
+    /// ```lua
     /// -- Simple Alias
     /// ---@alias <name> <type>
     ///
@@ -81,6 +100,14 @@ pub enum TagType {
     /// ```
     Alias(String, Option<Ty>),
     /// ```lua
+    /// ---@enum [(key)] <name>
+    /// local <name> = {
+    ///     ---[description]
+    ///     <key> = <value>, [-- description]
+    /// }
+    /// ```
+    Enum(bool, String, Vec<(String, String, Vec<String>)>),
+    /// ```lua
     /// ---| '<literal>' [# description]
     ///
     /// -- or
@@ -101,6 +128,36 @@ pub enum TagType {
     /// ```
     See(String),
     /// ```lua
+    /// ---@deprecated [message]
+    /// ```
+    Deprecated(Option<String>),
+    /// ```lua
+    /// ---@since <version>
+    /// ```
+    Since(String),
+    /// ```lua
+    /// ---@version <lua-version>[, lua-version...]
+    /// ```
+    Version(String),
+    /// ```lua
+    /// ---@note <text>
+    ///
+    /// ---@warning <text>
+    /// ```
+    Admonition(AdmonitionKind, String),
+    /// ```lua
+    /// ---@note [[
+    ///
+    /// ---@warning [[
+    /// ```
+    AdmonitionStart(AdmonitionKind),
+    /// ```lua
+    /// ---@note ]]
+    ///
+    /// ---@warning ]]
+    /// ```
+    AdmonitionEnd,
+    /// ```lua
     /// ---@usage [lang] `<code>`
     /// ```
     Usage(Option<String>, String),
@@ -118,9 +175,12 @@ pub enum TagType {
     Comment(String),
     /// Text nodes which are not needed
     Skip,
+    /// Empty line after a token, which separates the comment blocks
+    Blank,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Op {
     Deep(Vec<Op>),
     Dot(String),
@@ -149,6 +209,7 @@ impl Display for Op {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Scope {
     Public,
     Private,
@@ -156,10 +217,49 @@ pub enum Scope {
     Package,
 }
 
+/// Callouts which are shown separately from the description
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AdmonitionKind {
+    Note,
+    Warning,
+}
+
+impl Display for AdmonitionKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Note => f.write_str("Note"),
+            Self::Warning => f.write_str("Warning"),
+        }
+    }
+}
+
+/// Function attributes which are meaningful to the caller
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Attribute {
+    /// Must be called inside a coroutine
+    Async,
+    /// Return value must not be ignored
+    Nodiscard,
+}
+
+impl Display for Attribute {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Async => f.write_str("async"),
+            Self::Nodiscard => f.write_str("nodiscard"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Name {
     Req(String),
     Opt(String),
+    /// Bracketed key i.e., `[string]`, `["key"]` or `[1]`
+    Index(Ty),
 }
 
 impl Display for Name {
@@ -170,12 +270,59 @@ impl Display for Name {
                 f.write_str(n)?;
                 f.write_str("?")
             }
+            Self::Index(ty) => write!(f, "[{ty}]"),
+        }
+    }
+}
+
+/// Type parameter declared by `---@generic` or `---@class Name<T>`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Generic {
+    pub name: String,
+    pub parent: Option<Ty>,
+}
+
+impl Generic {
+    /// Formats the type parameters as `<T, U : parent>`, or empty if there are none
+    ///
+    /// ```
+    /// use lemmy_help::lexer::{Generic, Ty};
+    ///
+    /// let generics = [
+    ///     Generic { name: "T".into(), parent: None },
+    ///     Generic { name: "U".into(), parent: Some(Ty::Ref("table".into())) },
+    /// ];
+    ///
+    /// assert_eq!(Generic::angled(&generics), "<T, U : table>");
+    /// assert_eq!(Generic::angled(&[]), "");
+    /// ```
+    pub fn angled(generics: &[Generic]) -> String {
+        if generics.is_empty() {
+            return String::new();
+        }
+        let list = generics
+            .iter()
+            .map(|g| g.to_string())
+            .collect::<Vec<String>>()
+            .join(", ");
+        format!("<{list}>")
+    }
+}
+
+impl Display for Generic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.name)?;
+        if let Some(parent) = &self.parent {
+            write!(f, " : {parent}")?;
         }
+        Ok(())
     }
 }
 
 // Source: https://github.com/sumneko/lua-language-server/wiki/Annotations#documenting-types
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Ty {
     Nil,
     Any,
@@ -192,9 +339,96 @@ pub enum Ty {
     Member(Member),
     Array(Box<Ty>),
     Table(Option<(Box<Ty>, Box<Ty>)>),
-    Fun(Vec<(Name, Ty)>, Option<Vec<Ty>>),
+    Fun(Vec<(Name, Ty)>, Option<Vec<(Option<String>, Ty)>>),
     Dict(Vec<(Name, Ty)>),
-    Union(Box<Ty>, Box<Ty>),
+    Union(Vec<Ty>),
+    Opt(Box<Ty>),
+    /// Generic type application i.e., `Foo<string>`
+    Apply(String, Vec<Ty>),
+    /// Tuple i.e., `[integer, string]`
+    Tuple(Vec<Ty>),
+    /// Integer or float literal i.e., `1` or `-0.5`
+    Num(String),
+    /// Boolean literal i.e., `true` or `false`
+    Bool(bool),
+}
+
+impl Ty {
+    /// Creates a flat union of the given types without any duplicates. Nested unions are
+    /// merged into it, and a single type is returned as it is.
+    ///
+    /// ```
+    /// use lemmy_help::lexer::Ty;
+    ///
+    /// let ty = Ty::union([
+    ///     Ty::String,
+    ///     Ty::Union(vec![Ty::Number, Ty::String]),
+    ///     Ty::Array(Box::new(Ty::Union(vec![Ty::Nil, Ty::Boolean]))),
+    /// ]);
+    ///
+    /// assert_eq!(ty.to_string(), "string|number|(nil|boolean)[]");
+    /// assert_eq!(Ty::union([Ty::Nil, Ty::Nil]), Ty::Nil);
+    /// ```
+    pub fn union(types: impl IntoIterator<Item = Ty>) -> Ty {
+        let mut flat = Vec::new();
+        for ty in types {
+            let inner = match ty {
+                Self::Union(inner) => inner,
+                ty => vec![ty],
+            };
+            for ty in inner {
+                if !flat.contains(&ty) {
+                    flat.push(ty);
+                }
+            }
+        }
+        match <[Ty; 1]>::try_from(flat) {
+            Ok([ty]) => ty,
+            Err(flat) => Self::Union(flat),
+        }
+    }
+
+    /// Expands every optional type i.e., `T?` to `nil|T`
+    ///
+    /// ```
+    /// use lemmy_help::lexer::Ty;
+    ///
+    /// let ty = Ty::Array(Box::new(Ty::Opt(Box::new(Ty::String))));
+    ///
+    /// assert_eq!(ty.to_string(), "string?[]");
+    /// assert_eq!(ty.expand_opt().to_string(), "(nil|string)[]");
+    /// ```
+    pub fn expand_opt(&self) -> Ty {
+        fn list_like(args: &[(Name, Ty)]) -> Vec<(Name, Ty)> {
+            args.iter()
+                .map(|(n, t)| (n.clone(), t.expand_opt()))
+                .collect()
+        }
+
+        match self {
+            Self::Opt(ty) => Self::union([Self::Nil, ty.expand_opt()]),
+            Self::Array(ty) => Self::Array(Box::new(ty.expand_opt())),
+            Self::Table(kv) => Self::Table(
+                kv.as_ref()
+                    .map(|(k, v)| (Box::new(k.expand_opt()), Box::new(v.expand_opt()))),
+            ),
+            Self::Fun(args, ret) => Self::Fun(
+                list_like(args),
+                ret.as_ref().map(|ret| {
+                    ret.iter()
+                        .map(|(n, t)| (n.clone(), t.expand_opt()))
+                        .collect()
+                }),
+            ),
+            Self::Dict(kv) => Self::Dict(list_like(kv)),
+            Self::Union(types) => Self::union(types.iter().map(Self::expand_opt)),
+            Self::Apply(name, args) => {
+                Self::Apply(name.clone(), args.iter().map(Self::expand_opt).collect())
+            }
+            Self::Tuple(items) => Self::Tuple(items.iter().map(Self::expand_opt).collect()),
+            ty => ty.clone(),
+        }
+    }
 }
 
 impl Display for Ty {
@@ -219,10 +453,11 @@ impl Display for Ty {
             Self::Userdata => f.write_str("userdata"),
             Self::Lightuserdata => f.write_str("lightuserdata"),
             Self::Ref(id) => f.write_str(id),
-            Self::Array(ty) => {
-                f.write_str(&ty.to_string())?;
-                f.write_str("[]")
-            }
+            Self::Array(ty) => match ty.as_ref() {
+                // Otherwise `[]` would be applied to the last type of the union/return
+                Self::Union(..) | Self::Fun(_, Some(..)) => write!(f, "({ty})[]"),
+                _ => write!(f, "{ty}[]"),
+            },
             Self::Table(kv) => match kv {
                 Some((k, v)) => {
                     f.write_str("table<")?;
@@ -239,12 +474,20 @@ impl Display for Ty {
                 f.write_str(")")?;
                 if let Some(ret) = ret {
                     f.write_str(":")?;
-                    f.write_str(
-                        &ret.iter()
-                            .map(|r| r.to_string())
-                            .collect::<Vec<String>>()
-                            .join(","),
-                    )?;
+                    let named = ret.iter().any(|(n, _)| n.is_some());
+                    let ret = ret
+                        .iter()
+                        .map(|(n, t)| match n {
+                            Some(n) => format!("{n}:{t}"),
+                            None => t.to_string(),
+                        })
+                        .collect::<Vec<String>>()
+                        .join(",");
+                    if named {
+                        write!(f, "({ret})")?;
+                    } else {
+                        f.write_str(&ret)?;
+                    }
                 }
                 Ok(())
             }
@@ -253,11 +496,46 @@ impl Display for Ty {
                 f.write_str(&list_like(kv))?;
                 f.write_str("}")
             }
-            Self::Union(rhs, lhs) => {
-                f.write_str(&rhs.to_string())?;
-                f.write_str("|")?;
-                f.write_str(&lhs.to_string())
+            Self::Union(types) => f.write_str(
+                &types
+                    .iter()
+                    .map(|t| match t {
+                        // Otherwise the rest of the union would be read as the return type
+                        Self::Fun(_, Some(..)) => format!("({t})"),
+                        _ => t.to_string(),
+                    })
+                    .collect::<Vec<String>>()
+                    .join("|"),
+            ),
+            Self::Opt(ty) => match ty.as_ref() {
+                Self::Union(..) | Self::Fun(_, Some(..)) => write!(f, "({ty})?"),
+                _ => write!(f, "{ty}?"),
+            },
+            Self::Apply(name, args) => {
+                f.write_str(name)?;
+                f.write_str("<")?;
+                f.write_str(
+                    &args
+                        .iter()
+                        .map(|a| a.to_string())
+                        .collect::<Vec<String>>()
+                        .join(","),
+                )?;
+                f.write_str(">")
+            }
+            Self::Tuple(items) => {
+                f.write_str("[")?;
+                f.write_str(
+                    &items
+                        .iter()
+                        .map(|i| i.to_string())
+                        .collect::<Vec<String>>()
+                        .join(","),
+                )?;
+                f.write_str("]")
             }
+            Self::Num(num) => f.write_str(num),
+            Self::Bool(b) => write!(f, "{b}"),
             Self::Member(mem) => mem.fmt(f),
         }
     }
@@ -1,6 +1,24 @@
 #[cfg(feature = "vimdoc")]
 pub mod vimdoc;
 
+#[cfg(feature = "markdown")]
+pub mod markdown;
+
+#[cfg(feature = "html")]
+pub mod html;
+
+#[cfg(feature = "man")]
+pub mod man;
+
+#[cfg(feature = "meta")]
+pub mod meta;
+
+#[cfg(feature = "typescript")]
+pub mod typescript;
+
+#[cfg(feature = "ctags")]
+pub mod ctags;
+
 pub mod lexer;
 pub mod parser;
 
@@ -95,6 +113,14 @@ pub struct Settings {
     pub layout: Layout,
     /// Controls the indent width
     pub indent_width: usize,
+    /// Vimdoc text width, used to align the tags and draw the dividers
+    pub text_width: usize,
+    /// Reflow the descriptions to fit in the text width
+    pub wrap: bool,
+    /// Omit the `---@deprecated` items
+    pub omit_deprecated: bool,
+    /// Append a changelog section, grouped by the `---@since` version
+    pub changelog: bool,
 }
 
 impl Default for Settings {
@@ -107,6 +133,10 @@ impl Default for Settings {
             expand_opt: false,
             layout: Layout::default(),
             indent_width: 4,
+            text_width: 80,
+            wrap: false,
+            omit_deprecated: false,
+            changelog: false,
         }
     }
 }
@@ -182,7 +212,15 @@ impl LemmyHelp {
                 _ => export.to_owned(),
             };
 
+            // Everything under a deprecated module is also omitted
+            let mut omit = false;
             for ele in nodes {
+                if let Node::Module(m) = &ele {
+                    omit = settings.omit_deprecated && m.deprecated.is_some();
+                }
+                if omit || (settings.omit_deprecated && ele.is_deprecated()) {
+                    continue;
+                }
                 match ele {
                     Node::Export(..) => {}
                     Node::Func(mut func) => {
@@ -208,6 +246,9 @@ impl LemmyHelp {
                         self.nodes.push(Node::Alias(alias))
                     }
                     Node::Class(mut class) => {
+                        if settings.omit_deprecated {
+                            class.fields.retain(|f| f.deprecated.is_none());
+                        }
                         if settings.prefix_class {
                             class.prefix.right = Some(module.to_owned());
                         }
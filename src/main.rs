@@ -6,7 +6,12 @@ use cli::Cli;
 
 fn main() {
     match Cli::new() {
-        Ok(c) => c.run(),
+        Ok(c) => {
+            if let Err(e) = c.run() {
+                eprintln!("{e}");
+                exit(1)
+            }
+        }
         Err(e) => {
             eprintln!("{e}");
             exit(1)
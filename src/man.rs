@@ -0,0 +1,360 @@
+use std::fmt::Display;
+
+use crate::{
+    lexer::{Generic, Name, Scope, Ty},
+    parser::{
+        Alias, AliasKind, Brief, Class, Divider, Field, Func, Module, Node, Param, Return, See,
+        Tag, Type, Usage,
+    },
+    Accept, FromEmmy, Settings, Visitor,
+};
+
+/// Manual section used in the `.TH` title line
+const SECTION: u8 = 1;
+
+#[derive(Debug)]
+pub struct Man(String);
+
+impl Visitor for Man {
+    type R = String;
+    type S = Settings;
+
+    fn module(&self, n: &Module, _: &Self::S) -> Self::R {
+        format!(".SH \"{}\"\n", quote(n.desc.as_deref().unwrap_or(&n.name)))
+    }
+
+    fn divider(&self, _: &Divider, _: &Self::S) -> Self::R {
+        String::new()
+    }
+
+    fn brief(&self, n: &Brief, _: &Self::S) -> Self::R {
+        description(&n.desc)
+    }
+
+    fn tag(&self, _: &Tag, _: &Self::S) -> Self::R {
+        String::new()
+    }
+
+    fn func(&self, n: &Func, s: &Self::S) -> Self::R {
+        let args = n
+            .params
+            .iter()
+            .map(|p| p.name.to_string())
+            .collect::<Vec<String>>()
+            .join(", ");
+        let mut doc = format!(
+            ".SS \"{}\"\n",
+            quote(&format!(
+                "{}{}{}({args})",
+                n.prefix.left.as_deref().unwrap_or_default(),
+                n.op,
+                Generic::angled(&n.generics)
+            ))
+        );
+        if !n.desc.is_empty() {
+            doc.push_str(&description(&n.desc));
+        }
+        if !n.params.is_empty() {
+            doc.push_str(&heading("Parameters:"));
+            doc.push_str(&self.params(&n.params, s));
+        }
+        if !n.returns.is_empty() {
+            doc.push_str(&heading("Returns:"));
+            doc.push_str(&self.returns(&n.returns, s));
+        }
+        if !n.see.refs.is_empty() {
+            doc.push_str(&self.see(&n.see, s));
+        }
+        if let Some(usage) = &n.usage {
+            doc.push_str(&self.usage(usage, s));
+        }
+        doc
+    }
+
+    fn params(&self, n: &[Param], s: &Self::S) -> Self::R {
+        let mut doc = String::from(".RS\n");
+        for param in n {
+            let (name, ty) = match (s.expand_opt, &param.name) {
+                (true, Name::Opt(n)) => (
+                    n.to_string(),
+                    Ty::union([Ty::Nil, param.ty.expand_opt()]).to_string(),
+                ),
+                (true, n) => (n.to_string(), param.ty.expand_opt().to_string()),
+                (_, n) => (n.to_string(), param.ty.to_string()),
+            };
+            doc.push_str(&item(&name, &ty, &param.desc));
+        }
+        doc.push_str(".RE\n");
+        doc
+    }
+
+    fn r#returns(&self, n: &[Return], _: &Self::S) -> Self::R {
+        let mut doc = String::from(".RS\n");
+        for entry in n {
+            doc.push_str(".TP\n");
+            doc.push_str(&format!("\\fI{}\\fR\n", escape(&entry.ty.to_string())));
+            if entry.desc.is_empty() {
+                if let Some(name) = &entry.name {
+                    doc.push_str(&escape(name));
+                    doc.push('\n');
+                }
+            } else {
+                doc.push_str(&lines(&entry.desc));
+            }
+        }
+        doc.push_str(".RE\n");
+        doc
+    }
+
+    fn class(&self, n: &Class, s: &Self::S) -> Self::R {
+        let name = format!(
+            "{}{}{}",
+            n.name,
+            Generic::angled(&n.generics),
+            if n.parents.is_empty() {
+                String::new()
+            } else {
+                let parents = n
+                    .parents
+                    .iter()
+                    .map(|p| p.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                format!(" : {parents}")
+            }
+        );
+        let mut doc = format!(".SS \"{}\"\n", quote(&name));
+        if !n.desc.is_empty() {
+            doc.push_str(&description(&n.desc));
+        }
+        if !n.fields.is_empty() {
+            doc.push_str(&heading("Fields:"));
+            doc.push_str(&self.fields(&n.fields, s));
+        }
+        if !n.see.refs.is_empty() {
+            doc.push_str(&self.see(&n.see, s));
+        }
+        doc
+    }
+
+    fn fields(&self, n: &[Field], s: &Self::S) -> Self::R {
+        let mut doc = String::from(".RS\n");
+        for field in n {
+            if field.scope != Scope::Public {
+                continue;
+            }
+            let (name, ty) = match (s.expand_opt, &field.name) {
+                (true, Name::Opt(n)) => (
+                    n.to_string(),
+                    Ty::union([Ty::Nil, field.ty.expand_opt()]).to_string(),
+                ),
+                (true, n) => (n.to_string(), field.ty.expand_opt().to_string()),
+                (_, n) => (n.to_string(), field.ty.to_string()),
+            };
+            doc.push_str(&item(&name, &ty, &field.desc));
+        }
+        doc.push_str(".RE\n");
+        doc
+    }
+
+    fn alias(&self, n: &Alias, _: &Self::S) -> Self::R {
+        let mut doc = format!(".SS \"{}\"\n", quote(&n.name));
+        if !n.desc.is_empty() {
+            doc.push_str(&description(&n.desc));
+        }
+        match &n.kind {
+            AliasKind::Type(ty) => {
+                doc.push_str(&heading("Type:"));
+                doc.push_str(&format!(".RS\n\\fI{}\\fR\n.RE\n", escape(&ty.to_string())));
+            }
+            AliasKind::Enum(variants) => {
+                doc.push_str(&heading("Variants:"));
+                doc.push_str(".RS\n");
+                for (ty, desc) in variants {
+                    doc.push_str(".TP\n");
+                    doc.push_str(&format!("\\fI{}\\fR\n", escape(&ty.to_string())));
+                    if let Some(desc) = desc {
+                        doc.push_str(&escape(desc));
+                        doc.push('\n');
+                    }
+                }
+                doc.push_str(".RE\n");
+            }
+            AliasKind::Table(_, fields) => {
+                doc.push_str(&heading("Variants:"));
+                doc.push_str(".RS\n");
+                for (key, value, desc) in fields {
+                    doc.push_str(".TP\n");
+                    doc.push_str(&format!(
+                        "\\fB{}\\fR = \\fI{}\\fR\n",
+                        escape(key),
+                        escape(value)
+                    ));
+                    for line in desc {
+                        doc.push_str(&escape(line));
+                        doc.push('\n');
+                    }
+                }
+                doc.push_str(".RE\n");
+            }
+        }
+        doc
+    }
+
+    fn r#type(&self, n: &Type, s: &Self::S) -> Self::R {
+        let mut doc = format!(
+            ".SS \"{}\"\n",
+            quote(&format!(
+                "{}{}",
+                n.prefix.left.as_deref().unwrap_or_default(),
+                n.op
+            ))
+        );
+        let (extract, desc) = &n.desc;
+        if !extract.is_empty() {
+            doc.push_str(&description(extract));
+        }
+        doc.push_str(&heading("Type:"));
+        doc.push_str(".RS\n.TP\n");
+        doc.push_str(&format!("\\fI{}\\fR\n", escape(&n.ty.to_string())));
+        if let Some(desc) = desc {
+            doc.push_str(&escape(desc));
+            doc.push('\n');
+        }
+        doc.push_str(".RE\n");
+        if !n.see.refs.is_empty() {
+            doc.push_str(&self.see(&n.see, s));
+        }
+        if let Some(usage) = &n.usage {
+            doc.push_str(&self.usage(usage, s));
+        }
+        doc
+    }
+
+    fn see(&self, n: &See, _: &Self::S) -> Self::R {
+        let mut doc = heading("See:");
+        doc.push_str(".RS\n");
+        for reff in &n.refs {
+            doc.push_str(&format!("\\fB{}\\fR\n.br\n", escape(reff)));
+        }
+        doc.push_str(".RE\n");
+        doc
+    }
+
+    fn usage(&self, n: &Usage, _: &Self::S) -> Self::R {
+        let mut doc = heading("Usage:");
+        doc.push_str(".RS\n.EX\n");
+        doc.push_str(&escape(&n.code));
+        doc.push_str("\n.EE\n.RE\n");
+        doc
+    }
+
+    fn toc(&self, _: &str, nodes: &[Node], s: &Self::S) -> Self::R {
+        let mut doc = self.module(
+            &Module {
+                name: String::new(),
+                desc: Some("Table of Contents".into()),
+                deprecated: None,
+                admonitions: vec![],
+            },
+            s,
+        );
+        for nod in nodes {
+            if let Node::Module(x) = nod {
+                doc.push_str(".TP\n");
+                doc.push_str(&format!("\\fB{}\\fR\n", escape(&x.name)));
+                if let Some(desc) = &x.desc {
+                    doc.push_str(&escape(desc));
+                    doc.push('\n');
+                }
+            }
+        }
+        doc
+    }
+}
+
+impl FromEmmy for Man {
+    type Settings = Settings;
+    fn from_emmy(t: &impl crate::Nodes, s: &Self::Settings) -> Self {
+        let nodes = t.nodes();
+        let title = nodes.iter().find_map(|x| match x {
+            Node::Module(m) => Some(m.name.as_str()),
+            _ => None,
+        });
+        let mut shelf = Self(format!(
+            ".TH \"{}\" {SECTION}\n",
+            quote(title.unwrap_or_default())
+        ));
+        for node in nodes {
+            if let Node::Toc(x) = node {
+                shelf.0.push_str(&shelf.toc(x, nodes, s));
+            } else {
+                shelf.0.push_str(&node.accept(&shelf, s));
+            }
+        }
+        shelf
+    }
+}
+
+impl Display for Man {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.0.as_str())
+    }
+}
+
+// #################
+
+/// Escapes the roff control characters, so that the text is printed as is
+#[inline]
+fn escape(text: &str) -> String {
+    text.lines()
+        .map(|line| {
+            let line = line.replace('\\', "\\e").replace('-', "\\-");
+            if line.starts_with('.') || line.starts_with('\'') {
+                format!("\\&{line}")
+            } else {
+                line
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Escapes the text to be used inside a quoted macro argument
+#[inline]
+fn quote(text: &str) -> String {
+    escape(text).replace('"', "\"\"")
+}
+
+#[inline]
+fn lines(desc: &[String]) -> String {
+    let mut doc = String::new();
+    for line in desc {
+        if line.trim().is_empty() {
+            doc.push_str(".sp\n");
+        } else {
+            doc.push_str(&escape(line));
+            doc.push_str("\n.br\n");
+        }
+    }
+    doc
+}
+
+#[inline]
+fn description(desc: &[String]) -> String {
+    let mut doc = String::from(".PP\n");
+    doc.push_str(&lines(desc));
+    doc
+}
+
+#[inline]
+fn heading(name: &str) -> String {
+    format!(".PP\n\\fB{name}\\fR\n")
+}
+
+#[inline]
+fn item(name: &str, ty: &str, desc: &[String]) -> String {
+    let mut doc = format!(".TP\n\\fB{}\\fR (\\fI{}\\fR)\n", escape(name), escape(ty));
+    doc.push_str(&lines(desc));
+    doc
+}
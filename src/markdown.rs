@@ -0,0 +1,346 @@
+use std::fmt::Display;
+
+use crate::{
+    lexer::{Generic, Name, Scope, Ty},
+    parser::{
+        Alias, AliasKind, Brief, Class, Divider, Field, Func, Module, Node, Param, Return, See,
+        Tag, Type, Usage,
+    },
+    Accept, FromEmmy, Settings, Visitor,
+};
+
+#[derive(Debug)]
+pub struct Markdown(String);
+
+impl Visitor for Markdown {
+    type R = String;
+    type S = Settings;
+
+    fn module(&self, n: &Module, _: &Self::S) -> Self::R {
+        let mut doc = anchor(&n.name);
+        doc.push_str("## ");
+        doc.push_str(n.desc.as_deref().unwrap_or(&n.name));
+        doc.push('\n');
+        doc
+    }
+
+    fn divider(&self, _: &Divider, _: &Self::S) -> Self::R {
+        "---\n".into()
+    }
+
+    fn brief(&self, n: &Brief, _: &Self::S) -> Self::R {
+        let mut doc = n.desc.join("\n");
+        doc.push('\n');
+        doc
+    }
+
+    fn tag(&self, n: &Tag, _: &Self::S) -> Self::R {
+        anchor(&n.0)
+    }
+
+    fn func(&self, n: &Func, s: &Self::S) -> Self::R {
+        let args = n
+            .params
+            .iter()
+            .map(|p| p.name.to_string())
+            .collect::<Vec<String>>()
+            .join(", ");
+        let mut doc = heading(
+            &format!(
+                "{}{}{}({args})",
+                n.prefix.left.as_deref().unwrap_or_default(),
+                n.op,
+                Generic::angled(&n.generics)
+            ),
+            &format!("{}{}", n.prefix.right.as_deref().unwrap_or_default(), n.op),
+        );
+        if !n.desc.is_empty() {
+            doc.push('\n');
+            doc.push_str(&description(&n.desc));
+        }
+        if !n.params.is_empty() {
+            doc.push_str("\n**Parameters**\n\n");
+            doc.push_str(&self.params(&n.params, s));
+        }
+        if !n.returns.is_empty() {
+            doc.push_str("\n**Returns**\n\n");
+            doc.push_str(&self.returns(&n.returns, s));
+        }
+        if !n.see.refs.is_empty() {
+            doc.push_str(&self.see(&n.see, s));
+        }
+        if let Some(usage) = &n.usage {
+            doc.push_str(&self.usage(usage, s));
+        }
+        doc
+    }
+
+    fn params(&self, n: &[Param], s: &Self::S) -> Self::R {
+        let mut table = Table::new(["Name", "Type", "Description"]);
+        for param in n {
+            let (name, ty) = match (s.expand_opt, &param.name) {
+                (true, Name::Opt(n)) => (
+                    n.to_string(),
+                    Ty::union([Ty::Nil, param.ty.expand_opt()]).to_string(),
+                ),
+                (true, n) => (n.to_string(), param.ty.expand_opt().to_string()),
+                (_, n) => (n.to_string(), param.ty.to_string()),
+            };
+            table.add_row([code(&name), code(&ty), param.desc.join("\n")]);
+        }
+        table.to_string()
+    }
+
+    fn r#returns(&self, n: &[Return], _: &Self::S) -> Self::R {
+        let mut table = Table::new(["Type", "Description"]);
+        for entry in n {
+            table.add_row([
+                code(&entry.ty.to_string()),
+                if entry.desc.is_empty() {
+                    entry.name.clone().unwrap_or_default()
+                } else {
+                    entry.desc.join("\n")
+                },
+            ]);
+        }
+        table.to_string()
+    }
+
+    fn class(&self, n: &Class, s: &Self::S) -> Self::R {
+        let name = format!(
+            "{}{}{}",
+            n.name,
+            Generic::angled(&n.generics),
+            if n.parents.is_empty() {
+                String::new()
+            } else {
+                let parents = n
+                    .parents
+                    .iter()
+                    .map(|p| p.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                format!(" : {parents}")
+            }
+        );
+        let mut doc = match &n.prefix.right {
+            Some(prefix) => heading(&name, &format!("{prefix}.{}", n.name)),
+            None => heading(&name, &n.name),
+        };
+        if !n.desc.is_empty() {
+            doc.push('\n');
+            doc.push_str(&description(&n.desc));
+        }
+        if !n.fields.is_empty() {
+            doc.push_str("\n**Fields**\n\n");
+            doc.push_str(&self.fields(&n.fields, s));
+        }
+        if !n.see.refs.is_empty() {
+            doc.push_str(&self.see(&n.see, s));
+        }
+        doc
+    }
+
+    fn fields(&self, n: &[Field], s: &Self::S) -> Self::R {
+        let mut table = Table::new(["Name", "Type", "Description"]);
+        for field in n {
+            if field.scope != Scope::Public {
+                continue;
+            }
+            let (name, ty) = match (s.expand_opt, &field.name) {
+                (true, Name::Opt(n)) => (
+                    n.to_string(),
+                    Ty::union([Ty::Nil, field.ty.expand_opt()]).to_string(),
+                ),
+                (true, n) => (n.to_string(), field.ty.expand_opt().to_string()),
+                (_, n) => (n.to_string(), field.ty.to_string()),
+            };
+            table.add_row([code(&name), code(&ty), field.desc.join("\n")]);
+        }
+        table.to_string()
+    }
+
+    fn alias(&self, n: &Alias, _: &Self::S) -> Self::R {
+        let mut doc = match &n.prefix.right {
+            Some(prefix) => heading(&n.name, &format!("{prefix}.{}", n.name)),
+            None => heading(&n.name, &n.name),
+        };
+        if !n.desc.is_empty() {
+            doc.push('\n');
+            doc.push_str(&description(&n.desc));
+        }
+        match &n.kind {
+            AliasKind::Type(ty) => {
+                doc.push_str("\n**Type**\n\n");
+                doc.push_str(&code(&ty.to_string()));
+                doc.push('\n');
+            }
+            AliasKind::Enum(variants) => {
+                doc.push_str("\n**Variants**\n\n");
+                let mut table = Table::new(["Variant", "Description"]);
+                for (ty, desc) in variants {
+                    table.add_row([code(&ty.to_string()), desc.clone().unwrap_or_default()]);
+                }
+                doc.push_str(&table.to_string());
+            }
+            AliasKind::Table(_, fields) => {
+                doc.push_str("\n**Variants**\n\n");
+                let mut table = Table::new(["Key", "Value", "Description"]);
+                for (key, value, desc) in fields {
+                    table.add_row([code(key), code(value), desc.join("\n")]);
+                }
+                doc.push_str(&table.to_string());
+            }
+        }
+        doc
+    }
+
+    fn r#type(&self, n: &Type, s: &Self::S) -> Self::R {
+        let mut doc = heading(
+            &format!("{}{}", n.prefix.left.as_deref().unwrap_or_default(), n.op),
+            &format!("{}{}", n.prefix.right.as_deref().unwrap_or_default(), n.op),
+        );
+        let (extract, desc) = &n.desc;
+        if !extract.is_empty() {
+            doc.push('\n');
+            doc.push_str(&description(extract));
+        }
+        doc.push_str("\n**Type**\n\n");
+        let mut table = Table::new(["Type", "Description"]);
+        table.add_row([code(&n.ty.to_string()), desc.clone().unwrap_or_default()]);
+        doc.push_str(&table.to_string());
+        if !n.see.refs.is_empty() {
+            doc.push_str(&self.see(&n.see, s));
+        }
+        if let Some(usage) = &n.usage {
+            doc.push_str(&self.usage(usage, s));
+        }
+        doc
+    }
+
+    fn see(&self, n: &See, _: &Self::S) -> Self::R {
+        let mut doc = String::from("\n**See**\n\n");
+        for reff in &n.refs {
+            doc.push_str(&format!("- [`{reff}`](#{reff})\n"));
+        }
+        doc
+    }
+
+    fn usage(&self, n: &Usage, _: &Self::S) -> Self::R {
+        let mut doc = String::from("\n**Usage**\n\n```");
+        doc.push_str(n.lang.as_deref().unwrap_or("lua"));
+        doc.push('\n');
+        doc.push_str(&n.code);
+        doc.push_str("\n```\n");
+        doc
+    }
+
+    fn toc(&self, n: &str, nodes: &[Node], s: &Self::S) -> Self::R {
+        let mut doc = self.module(
+            &Module {
+                name: n.to_string(),
+                desc: Some("Table of Contents".into()),
+                deprecated: None,
+                admonitions: vec![],
+            },
+            s,
+        );
+        doc.push('\n');
+        for nod in nodes {
+            if let Node::Module(x) = nod {
+                doc.push_str(&format!(
+                    "- [{}](#{})\n",
+                    x.desc.as_deref().unwrap_or(&x.name),
+                    x.name
+                ));
+            }
+        }
+        doc
+    }
+}
+
+impl FromEmmy for Markdown {
+    type Settings = Settings;
+    fn from_emmy(t: &impl crate::Nodes, s: &Self::Settings) -> Self {
+        let mut shelf = Self(String::new());
+        let nodes = t.nodes();
+        for node in nodes {
+            if let Node::Toc(x) = node {
+                shelf.0.push_str(&shelf.toc(x, nodes, s));
+            } else {
+                shelf.0.push_str(&node.accept(&shelf, s));
+            }
+            shelf.0.push('\n');
+        }
+        shelf
+    }
+}
+
+impl Display for Markdown {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.0.as_str())
+    }
+}
+
+// #################
+
+struct Table<const N: usize> {
+    header: [&'static str; N],
+    rows: Vec<[String; N]>,
+}
+
+impl<const N: usize> Table<N> {
+    pub fn new(header: [&'static str; N]) -> Self {
+        Self {
+            header,
+            rows: vec![],
+        }
+    }
+
+    pub fn add_row(&mut self, row: [String; N]) -> &Self {
+        self.rows.push(row);
+        self
+    }
+}
+
+impl<const N: usize> std::fmt::Display for Table<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "| {} |", self.header.join(" | "))?;
+        writeln!(f, "|{}", " --- |".repeat(N))?;
+        for row in &self.rows {
+            let cells = row.iter().map(|c| cell(c)).collect::<Vec<String>>();
+            writeln!(f, "| {} |", cells.join(" | ").trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+/// Escapes the content so that it can be used inside a table cell
+#[inline]
+fn cell(content: &str) -> String {
+    content.trim_end().replace('|', "\\|").replace('\n', "<br>")
+}
+
+#[inline]
+fn code(content: &str) -> String {
+    format!("`{content}`")
+}
+
+#[inline]
+fn anchor(name: &str) -> String {
+    format!("<a name=\"{name}\"></a>\n")
+}
+
+#[inline]
+fn heading(name: &str, tag: &str) -> String {
+    let mut doc = anchor(tag);
+    doc.push_str(&format!("### `{name}`\n"));
+    doc
+}
+
+#[inline]
+fn description(desc: &[String]) -> String {
+    let mut d = desc.join("\n");
+    d.push('\n');
+    d
+}
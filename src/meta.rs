@@ -0,0 +1,311 @@
+use std::fmt::Display;
+
+use crate::{
+    lexer::{Generic, Member, Scope},
+    parser::{
+        Alias, AliasKind, Brief, Class, Deprecated, Divider, Field, Func, Module, Node, Param,
+        Return, See, Tag, Type, Usage,
+    },
+    Accept, FromEmmy, Settings, Visitor,
+};
+
+/// LuaLS definition file i.e., a lua file with `---@meta` at the top that contains only the
+/// annotations and the signatures of the public API without the implementation.
+#[derive(Debug)]
+pub struct Meta(String);
+
+impl Visitor for Meta {
+    type R = String;
+    type S = Settings;
+
+    fn module(&self, _: &Module, _: &Self::S) -> Self::R {
+        String::new()
+    }
+
+    fn divider(&self, _: &Divider, _: &Self::S) -> Self::R {
+        String::new()
+    }
+
+    fn brief(&self, _: &Brief, _: &Self::S) -> Self::R {
+        String::new()
+    }
+
+    fn tag(&self, _: &Tag, _: &Self::S) -> Self::R {
+        String::new()
+    }
+
+    fn func(&self, n: &Func, s: &Self::S) -> Self::R {
+        let mut doc = comment(&n.desc);
+        doc.push_str(&deprecated(&n.deprecated));
+        doc.push_str(&version(&n.version));
+        for attr in &n.attributes {
+            doc.push_str(&format!("---@{attr}\n"));
+        }
+        if !n.generics.is_empty() {
+            let generics = n
+                .generics
+                .iter()
+                .map(|g| g.to_string())
+                .collect::<Vec<String>>()
+                .join(", ");
+            doc.push_str(&format!("---@generic {generics}\n"));
+        }
+        doc.push_str(&self.params(&n.params, s));
+        doc.push_str(&self.returns(&n.returns, s));
+        for overload in &n.overloads {
+            doc.push_str(&format!("---@overload {overload}\n"));
+        }
+        doc.push_str(&self.see(&n.see, s));
+        let args = n
+            .params
+            .iter()
+            .map(|p| p.name.to_string().trim_end_matches('?').to_owned())
+            .collect::<Vec<String>>()
+            .join(", ");
+        doc.push_str(&format!(
+            "function {}{}({args}) end\n",
+            n.prefix.left.as_deref().unwrap_or_default(),
+            n.op
+        ));
+        doc
+    }
+
+    fn params(&self, n: &[Param], _: &Self::S) -> Self::R {
+        let mut doc = String::new();
+        for param in n {
+            doc.push_str(&format!("---@param {} {}", param.name, param.ty));
+            doc.push_str(&inline(&param.desc));
+        }
+        doc
+    }
+
+    fn r#returns(&self, n: &[Return], _: &Self::S) -> Self::R {
+        let mut doc = String::new();
+        for entry in n {
+            doc.push_str(&format!("---@return {}", entry.ty));
+            match (&entry.name, entry.desc.split_first()) {
+                (Some(name), _) => {
+                    doc.push(' ');
+                    doc.push_str(name);
+                    doc.push_str(&inline(&entry.desc));
+                }
+                (None, Some((first, rest))) => {
+                    doc.push_str(&format!(" #{first}\n"));
+                    doc.push_str(&comment(rest));
+                }
+                (None, None) => doc.push('\n'),
+            }
+        }
+        doc
+    }
+
+    fn class(&self, n: &Class, s: &Self::S) -> Self::R {
+        let mut doc = comment(&n.desc);
+        doc.push_str(&deprecated(&n.deprecated));
+        doc.push_str(&version(&n.version));
+        doc.push_str("---@class ");
+        if !n.attributes.is_empty() {
+            doc.push_str(&format!("({}) ", n.attributes.join(", ")));
+        }
+        doc.push_str(&format!("{}{}", n.name, Generic::angled(&n.generics)));
+        if !n.parents.is_empty() {
+            let parents = n
+                .parents
+                .iter()
+                .map(|p| p.to_string())
+                .collect::<Vec<String>>()
+                .join(", ");
+            doc.push_str(&format!(" : {parents}"));
+        }
+        doc.push('\n');
+        doc.push_str(&self.fields(&n.fields, s));
+        for op in &n.operators {
+            match &op.operand {
+                Some(operand) => doc.push_str(&format!(
+                    "---@operator {}({operand}): {}\n",
+                    op.kind, op.result
+                )),
+                None => doc.push_str(&format!("---@operator {}: {}\n", op.kind, op.result)),
+            }
+        }
+        doc.push_str(&self.see(&n.see, s));
+        doc
+    }
+
+    fn fields(&self, n: &[Field], _: &Self::S) -> Self::R {
+        let mut doc = String::new();
+        for field in n {
+            if field.scope != Scope::Public {
+                continue;
+            }
+            // Only the first line can be written after the field, rest goes above it
+            let (first, rest) = match field.desc.split_first() {
+                Some((first, rest)) => (Some(first), rest),
+                None => (None, &field.desc[..]),
+            };
+            doc.push_str(&comment(rest));
+            doc.push_str(&deprecated(&field.deprecated));
+            doc.push_str(&format!("---@field {} {}", field.name, field.ty));
+            if let Some(first) = first {
+                doc.push(' ');
+                doc.push_str(first);
+            }
+            doc.push('\n');
+        }
+        doc
+    }
+
+    fn alias(&self, n: &Alias, _: &Self::S) -> Self::R {
+        let mut doc = comment(&n.desc);
+        doc.push_str(&deprecated(&n.deprecated));
+        doc.push_str(&version(&n.version));
+        match &n.kind {
+            AliasKind::Type(ty) => doc.push_str(&format!("---@alias {} {ty}\n", n.name)),
+            AliasKind::Enum(variants) => {
+                doc.push_str(&format!("---@alias {}\n", n.name));
+                for (member, desc) in variants {
+                    let member = match member {
+                        Member::Literal(lit) => format!("'{lit}'"),
+                        Member::Ident(ident) => format!("`{ident}`"),
+                    };
+                    match desc {
+                        Some(desc) => doc.push_str(&format!("---| {member} # {desc}\n")),
+                        None => doc.push_str(&format!("---| {member}\n")),
+                    }
+                }
+            }
+            AliasKind::Table(key, fields) => {
+                match key {
+                    true => doc.push_str(&format!("---@enum (key) {}\n", n.name)),
+                    false => doc.push_str(&format!("---@enum {}\n", n.name)),
+                }
+                // Fields of a table, like `M.Mode`, can't be declared as local
+                if !n.name.contains('.') {
+                    doc.push_str("local ");
+                }
+                doc.push_str(&format!("{} = {{\n", n.name));
+                for (key, value, desc) in fields {
+                    for line in desc {
+                        doc.push_str(&format!("    ---{line}\n"));
+                    }
+                    doc.push_str(&format!("    {key} = {value},\n"));
+                }
+                doc.push_str("}\n");
+            }
+        }
+        doc
+    }
+
+    fn r#type(&self, n: &Type, s: &Self::S) -> Self::R {
+        let (extract, desc) = &n.desc;
+        let mut doc = comment(extract);
+        doc.push_str(&deprecated(&n.deprecated));
+        doc.push_str(&version(&n.version));
+        doc.push_str(&format!("---@type {}", n.ty));
+        if let Some(desc) = desc {
+            doc.push(' ');
+            doc.push_str(desc);
+        }
+        doc.push('\n');
+        doc.push_str(&self.see(&n.see, s));
+        doc.push_str(&format!(
+            "{}{} = nil\n",
+            n.prefix.left.as_deref().unwrap_or_default(),
+            n.op
+        ));
+        doc
+    }
+
+    fn see(&self, n: &See, _: &Self::S) -> Self::R {
+        let mut doc = String::new();
+        for reff in &n.refs {
+            doc.push_str(&format!("---@see {reff}\n"));
+        }
+        doc
+    }
+
+    fn usage(&self, _: &Usage, _: &Self::S) -> Self::R {
+        String::new()
+    }
+
+    fn toc(&self, _: &str, _: &[Node], _: &Self::S) -> Self::R {
+        String::new()
+    }
+}
+
+impl FromEmmy for Meta {
+    type Settings = Settings;
+    fn from_emmy(t: &impl crate::Nodes, s: &Self::Settings) -> Self {
+        let mut shelf = Self(String::from("---@meta\n"));
+        // Every exported table needs to be declared before any function or type can use it
+        let mut tables = Vec::new();
+        for node in t.nodes() {
+            let prefix = match node {
+                Node::Func(x) => x.prefix.left.as_deref(),
+                Node::Type(x) => x.prefix.left.as_deref(),
+                Node::Class(..) | Node::Alias(..) => None,
+                _ => continue,
+            };
+            shelf.0.push('\n');
+            if let Some(prefix) = prefix {
+                if !tables.contains(&prefix) {
+                    shelf.0.push_str(&format!("local {prefix} = {{}}\n\n"));
+                    tables.push(prefix);
+                }
+            }
+            shelf.0.push_str(&node.accept(&shelf, s));
+        }
+        // Only a single table can be returned from the module
+        if let [export] = tables[..] {
+            shelf.0.push_str(&format!("\nreturn {export}\n"));
+        }
+        shelf
+    }
+}
+
+impl Display for Meta {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.0.as_str())
+    }
+}
+
+// #################
+
+#[inline]
+fn comment(desc: &[String]) -> String {
+    let mut doc = String::new();
+    for line in desc {
+        doc.push_str("---");
+        doc.push_str(line);
+        doc.push('\n');
+    }
+    doc
+}
+
+#[inline]
+fn deprecated(n: &Option<Deprecated>) -> String {
+    match n {
+        Some(Deprecated { message: Some(msg) }) => format!("---@deprecated {msg}\n"),
+        Some(Deprecated { message: None }) => String::from("---@deprecated\n"),
+        None => String::new(),
+    }
+}
+
+#[inline]
+fn version(n: &Option<String>) -> String {
+    n.as_ref()
+        .map_or(String::new(), |v| format!("---@version {v}\n"))
+}
+
+/// Writes the first line after the tag and rest of them as comments below it
+#[inline]
+fn inline(desc: &[String]) -> String {
+    match desc.split_first() {
+        Some((first, rest)) => {
+            let mut doc = format!(" {first}\n");
+            doc.push_str(&comment(rest));
+            doc
+        }
+        None => String::from("\n"),
+    }
+}
@@ -1,3 +1,5 @@
+use std::ops::Range;
+
 use chumsky::{
     prelude::{any, choice, Simple},
     select, Parser, Stream,
@@ -5,13 +7,14 @@ use chumsky::{
 
 use crate::{
     lexer::{Lexer, TagType},
-    parser::{Alias, Brief, Class, Divider, Func, Module, Tag, Type},
+    parser::{Alias, Attached, Brief, Class, Divider, Func, Module, Tag, Type},
     Accept, Visitor,
 };
 
 use super::impl_parse;
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Node {
     Module(Module),
     Divider(Divider),
@@ -61,6 +64,9 @@ impl<T: Visitor> Accept<T> for Node {
     }
 }
 
+/// Node along with its char range in the source
+pub type Spanned = (Node, Range<usize>);
+
 impl Node {
     fn init() -> impl Parser<TagType, Vec<Node>, Error = Simple<TagType>> {
         Node::parse().repeated().flatten()
@@ -86,9 +92,68 @@ impl Node {
     /// assert!(!nodes.is_empty());
     /// ```
     pub fn new(src: &str) -> Result<Vec<Node>, Vec<Simple<TagType>>> {
-        let tokens = Lexer::init().parse(src).unwrap();
+        let tokens = Self::tokens(src);
         let stream = Stream::from_iter(src.len()..src.len() + 1, tokens.into_iter());
 
         Node::init().parse(stream)
     }
+
+    /// Similar to [`Node::new`], but also returns the span i.e., char range of each node
+    ///
+    /// ```
+    /// let src = r#"
+    /// ---@class Human
+    /// "#;
+    ///
+    /// let nodes = lemmy_help::parser::Node::spanned(src).unwrap();
+    /// assert_eq!(nodes[0].1, 0..17);
+    /// ```
+    pub fn spanned(src: &str) -> Result<Vec<Spanned>, Vec<Simple<TagType>>> {
+        let tokens = Self::tokens(src);
+        let len = src.chars().count();
+        let stream = Stream::from_iter(len..len + 1, tokens.into_iter());
+
+        Node::parse()
+            .map_with_span(|node, span| node.map(|n| (n, span)))
+            .repeated()
+            .flatten()
+            .parse(stream)
+    }
+
+    /// Tokens of the source along with a [`TagType::Blank`] for every empty line before the
+    /// [`Attached`] tags, otherwise the trailing tags of a class or alias can't be told apart
+    /// from the leading tags of the next block
+    fn tokens(src: &str) -> Vec<(TagType, Range<usize>)> {
+        let chars: Vec<char> = src.chars().collect();
+        let mut tokens: Vec<(TagType, Range<usize>)> = vec![];
+        for (tag, span) in Lexer::init().parse(src).unwrap() {
+            if let Some((_, prev)) = tokens.last() {
+                // Every token includes the whitespace after it
+                let blank = chars[prev.clone()]
+                    .iter()
+                    .rev()
+                    .take_while(|c| c.is_whitespace())
+                    .filter(|c| **c == '\n')
+                    .count()
+                    > 1;
+                if blank && Attached::starts(&tag) {
+                    tokens.push((TagType::Blank, span.start..span.start));
+                }
+            }
+            tokens.push((tag, span));
+        }
+        tokens
+    }
+
+    /// Whether the node is marked with `---@deprecated`
+    pub fn is_deprecated(&self) -> bool {
+        match self {
+            Self::Module(x) => x.deprecated.is_some(),
+            Self::Func(x) => x.deprecated.is_some(),
+            Self::Class(x) => x.deprecated.is_some(),
+            Self::Alias(x) => x.deprecated.is_some(),
+            Self::Type(x) => x.deprecated.is_some(),
+            _ => false,
+        }
+    }
 }
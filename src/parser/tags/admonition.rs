@@ -0,0 +1,30 @@
+use chumsky::{
+    primitive::{choice, just},
+    select, Parser,
+};
+
+use crate::{
+    lexer::{AdmonitionKind, TagType},
+    parser::impl_parse,
+};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Admonition {
+    pub kind: AdmonitionKind,
+    pub desc: Vec<String>,
+}
+
+impl_parse!(Admonition, {
+    choice((
+        select! {
+            TagType::AdmonitionStart(kind) => kind
+        }
+        .then(select! { TagType::Comment(x) => x }.repeated())
+        .then_ignore(just(TagType::AdmonitionEnd))
+        .map(|(kind, desc)| Self { kind, desc }),
+        select! {
+            TagType::Admonition(kind, x) => Self { kind, desc: vec![x] }
+        },
+    ))
+});
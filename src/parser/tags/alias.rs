@@ -2,20 +2,29 @@ use chumsky::{prelude::choice, select, Parser};
 
 use crate::{
     lexer::{Member, TagType, Ty},
-    parser::{impl_parse, Prefix},
+    parser::{impl_parse, Admonition, Attached, Attachments, Deprecated, Prefix},
     Accept, Visitor,
 };
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AliasKind {
     Type(Ty),
     Enum(Vec<(Member, Option<String>)>),
+    /// `---@enum` table i.e., whether the keys are used as the values with `(key)`, and the
+    /// key, value and description of each field
+    Table(bool, Vec<(String, String, Vec<String>)>),
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Alias {
     pub name: String,
     pub desc: Vec<String>,
+    pub deprecated: Option<Deprecated>,
+    pub since: Option<String>,
+    pub version: Option<String>,
+    pub admonitions: Vec<Admonition>,
     pub kind: AliasKind,
     pub prefix: Prefix,
 }
@@ -25,6 +34,7 @@ impl_parse!(Alias, {
         TagType::Comment(x) => x,
     }
     .repeated()
+    .then(Attached::parse().repeated())
     .then(choice((
         select! {
             TagType::Alias(name, Some(ty)) => (name, AliasKind::Type(ty))
@@ -36,12 +46,31 @@ impl_parse!(Alias, {
             .repeated()
             .map(AliasKind::Enum),
         ),
+        select! {
+            TagType::Enum(key, name, fields) => (name, AliasKind::Table(key, fields))
+        },
     )))
-    .map(|(desc, (name, kind))| Self {
-        name,
-        desc,
-        kind,
-        prefix: Prefix::default(),
+    .then(Attached::adjacent().repeated())
+    .map(|(((desc, before), (name, kind)), after)| {
+        let mut attached = Attachments::default();
+        attached.extend(before.into_iter().chain(after));
+        let Attachments {
+            deprecated,
+            since,
+            version,
+            admonitions,
+        } = attached;
+
+        Self {
+            name,
+            desc,
+            deprecated,
+            since,
+            version,
+            admonitions,
+            kind,
+            prefix: Prefix::default(),
+        }
     })
 });
 
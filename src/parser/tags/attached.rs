@@ -0,0 +1,75 @@
+use chumsky::{
+    prelude::{choice, just, Simple},
+    select, Parser,
+};
+
+use crate::{
+    lexer::TagType,
+    parser::{impl_parse, Admonition, Deprecated},
+};
+
+/// Tags which can be placed anywhere in the block of a function, class, alias or type
+#[derive(Debug, Clone)]
+pub(crate) enum Attached {
+    Deprecated(Deprecated),
+    Since(String),
+    Version(String),
+    Admonition(Admonition),
+}
+
+impl_parse!(Attached, {
+    just(TagType::Blank).or_not().ignore_then(Self::adjacent())
+});
+
+impl Attached {
+    /// Same as [`Attached::parse`] but without an empty line before the tag, as the tags after
+    /// an empty line could be the leading tags of the next block
+    pub fn adjacent() -> impl Parser<TagType, Self, Error = Simple<TagType>> {
+        choice((
+            Deprecated::parse().map(Self::Deprecated),
+            select! {
+                TagType::Since(x) => Self::Since(x),
+                TagType::Version(x) => Self::Version(x),
+            },
+            Admonition::parse().map(Self::Admonition),
+        ))
+    }
+
+    /// Whether the token starts one of the tags
+    pub fn starts(tag: &TagType) -> bool {
+        matches!(
+            tag,
+            TagType::Deprecated(..)
+                | TagType::Since(..)
+                | TagType::Version(..)
+                | TagType::Admonition(..)
+                | TagType::AdmonitionStart(..)
+        )
+    }
+}
+
+/// Every [`Attached`] tag of a block, the last one wins if a tag is repeated
+#[derive(Debug, Default)]
+pub(crate) struct Attachments {
+    pub deprecated: Option<Deprecated>,
+    pub since: Option<String>,
+    pub version: Option<String>,
+    pub admonitions: Vec<Admonition>,
+}
+
+impl Attachments {
+    pub fn push(&mut self, tag: Attached) {
+        match tag {
+            Attached::Deprecated(x) => self.deprecated = Some(x),
+            Attached::Since(x) => self.since = Some(x),
+            Attached::Version(x) => self.version = Some(x),
+            Attached::Admonition(x) => self.admonitions.push(x),
+        }
+    }
+}
+
+impl Extend<Attached> for Attachments {
+    fn extend<I: IntoIterator<Item = Attached>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|tag| self.push(tag));
+    }
+}
@@ -1,17 +1,22 @@
-use chumsky::{select, Parser};
+use chumsky::{
+    prelude::{choice, just},
+    select, Parser,
+};
 
 use crate::{
-    lexer::{Name, Scope, TagType, Ty},
-    parser::{impl_parse, Prefix, See},
+    lexer::{Generic, Name, Scope, TagType, Ty},
+    parser::{impl_parse, Admonition, Attached, Attachments, Deprecated, Prefix, See},
     Accept, Visitor,
 };
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Field {
     pub scope: Scope,
     pub name: Name,
     pub ty: Ty,
     pub desc: Vec<String>,
+    pub deprecated: Option<Deprecated>,
 }
 
 impl_parse!(Field, {
@@ -19,10 +24,16 @@ impl_parse!(Field, {
         TagType::Comment(x) => x,
     }
     .repeated()
+    .then(
+        just(TagType::Blank)
+            .or_not()
+            .ignore_then(Deprecated::parse())
+            .or_not(),
+    )
     .then(select! {
         TagType::Field(scope, name, ty, desc) => (scope, name, ty, desc)
     })
-    .map(|(header, (scope, name, ty, desc))| {
+    .map(|((header, deprecated), (scope, name, ty, desc))| {
         let desc = match desc {
             Some(d) => {
                 let mut new_desc = Vec::with_capacity(header.len() + 1);
@@ -38,33 +49,120 @@ impl_parse!(Field, {
             name,
             ty,
             desc,
+            deprecated,
         }
     })
 });
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Operator {
+    /// Metamethod name without the `__` i.e., `add`, `unm`, `call`
+    pub kind: String,
+    pub operand: Option<Ty>,
+    pub result: Ty,
+}
+
+impl_parse!(Operator, {
+    select! {
+        TagType::Operator(kind, operand, result) => Self { kind, operand, result }
+    }
+});
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Class {
     pub name: String,
-    pub parent: Option<String>,
+    pub generics: Vec<Generic>,
+    /// Class attributes i.e., `exact` or `partial`
+    pub attributes: Vec<String>,
+    pub parents: Vec<Ty>,
     pub desc: Vec<String>,
+    pub deprecated: Option<Deprecated>,
+    pub since: Option<String>,
+    pub version: Option<String>,
+    pub admonitions: Vec<Admonition>,
     pub fields: Vec<Field>,
+    pub operators: Vec<Operator>,
     pub see: See,
     pub prefix: Prefix,
 }
 
+/// Tags of a class which can be placed in any order after the `---@class`
+enum Item {
+    Field(Field),
+    Operator(Operator),
+    Attached(Attached),
+    See(String),
+}
+
 impl_parse!(Class, {
     select! { TagType::Comment(c) => c }
         .repeated()
-        .then(select! { TagType::Class(name, parent) => (name, parent) })
-        .then(Field::parse().repeated())
-        .then(See::parse())
-        .map(|(((desc, (name, parent)), fields), see)| Self {
-            name,
-            parent,
-            desc,
-            fields,
-            see,
-            prefix: Prefix::default(),
+        .then(Attached::parse().repeated())
+        .then(select! {
+            TagType::Class(attrs, name, generics, parents) => (attrs, name, generics, parents)
+        })
+        .then(
+            choice((
+                Field::parse().map(|x| vec![Item::Field(x)]),
+                Operator::parse().map(|x| vec![Item::Operator(x)]),
+                select! { TagType::See(x) => vec![Item::See(x)] },
+                Attached::adjacent().map(|x| vec![Item::Attached(x)]),
+                // After an empty line, the tags only belong to the class if it continues
+                Attached::parse()
+                    .repeated()
+                    .at_least(1)
+                    .then_ignore(
+                        choice((
+                            Field::parse().ignored(),
+                            Operator::parse().ignored(),
+                            select! { TagType::See(_) => () },
+                        ))
+                        .rewind(),
+                    )
+                    .map(|x| x.into_iter().map(Item::Attached).collect()),
+            ))
+            .repeated()
+            .flatten(),
+        )
+        .map(|(((desc, before), header), items)| {
+            let (attributes, name, generics, parents) = header;
+            let mut attached = Attachments::default();
+            attached.extend(before);
+            let mut fields = vec![];
+            let mut operators = vec![];
+            let mut refs = vec![];
+            for item in items {
+                match item {
+                    Item::Field(x) => fields.push(x),
+                    Item::Operator(x) => operators.push(x),
+                    Item::Attached(x) => attached.push(x),
+                    Item::See(x) => refs.push(x),
+                }
+            }
+            let Attachments {
+                deprecated,
+                since,
+                version,
+                admonitions,
+            } = attached;
+
+            Self {
+                name,
+                generics,
+                attributes,
+                parents,
+                desc,
+                deprecated,
+                since,
+                version,
+                admonitions,
+                fields,
+                operators,
+                see: See { refs },
+                prefix: Prefix::default(),
+            }
         })
 });
 
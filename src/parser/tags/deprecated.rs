@@ -0,0 +1,13 @@
+use chumsky::select;
+
+use crate::{lexer::TagType, parser::impl_parse};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Deprecated {
+    pub message: Option<String>,
+}
+
+impl_parse!(Deprecated, {
+    select! { TagType::Deprecated(message) => Self { message } }
+});
@@ -1,14 +1,15 @@
-use chumsky::{select, Parser};
+use chumsky::{prelude::choice, select, Parser};
 
 use crate::{
-    lexer::{Name, Op, TagType, Ty},
-    parser::{impl_parse, Prefix, See},
+    lexer::{Attribute, Generic, Name, Op, TagType, Ty},
+    parser::{impl_parse, Admonition, Attached, Attachments, Deprecated, Prefix, See},
     Accept, Visitor,
 };
 
 use super::Usage;
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Param {
     pub name: Name,
     pub ty: Ty,
@@ -35,6 +36,7 @@ impl_parse!(Param, {
 });
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Return {
     pub ty: Ty,
     pub name: Option<String>,
@@ -62,40 +64,109 @@ impl_parse!(Return, {
 });
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Func {
     pub op: Op,
     pub prefix: Prefix,
     pub desc: Vec<String>,
+    pub deprecated: Option<Deprecated>,
+    pub since: Option<String>,
+    pub version: Option<String>,
+    pub admonitions: Vec<Admonition>,
+    pub attributes: Vec<Attribute>,
+    pub generics: Vec<Generic>,
     pub params: Vec<Param>,
     pub returns: Vec<Return>,
+    pub overloads: Vec<Ty>,
     pub see: See,
     pub usage: Option<Usage>,
 }
 
+/// Tags of a function which can be placed in any order
+enum Item {
+    /// Description lines written after a tag which doesn't take them
+    Comment(String),
+    Attached(Attached),
+    Attribute(Attribute),
+    Generic(Vec<Generic>),
+    Param(Param),
+    Return(Return),
+    Overload(Ty),
+    See(String),
+    Usage(Usage),
+}
+
 impl_parse!(Func, {
     select! {
         TagType::Comment(x) => x,
     }
     .repeated()
-    .then(Param::parse().repeated())
-    .then(Return::parse().repeated())
-    .then(See::parse())
-    .then(Usage::parse().or_not())
+    .then(
+        choice((
+            Attached::parse().map(Item::Attached),
+            select! {
+                TagType::Comment(x) => Item::Comment(x),
+                TagType::Attribute(x) => Item::Attribute(x),
+                TagType::Generic(x) => Item::Generic(x),
+                TagType::Overload(x) => Item::Overload(x),
+                TagType::See(x) => Item::See(x),
+            },
+            Param::parse().map(Item::Param),
+            Return::parse().map(Item::Return),
+            Usage::parse().map(Item::Usage),
+        ))
+        .repeated(),
+    )
     .then(select! { TagType::Func(prefix, op) => (prefix, op) })
-    .map(
-        |(((((desc, params), returns), see), usage), (prefix, op))| Self {
+    .map(|((mut desc, items), (prefix, op))| {
+        let mut attached = Attachments::default();
+        let mut attributes = vec![];
+        let mut generics = vec![];
+        let mut params = vec![];
+        let mut returns = vec![];
+        let mut overloads = vec![];
+        let mut refs = vec![];
+        let mut usage = None;
+        for item in items {
+            match item {
+                Item::Comment(x) => desc.push(x),
+                Item::Attached(x) => attached.push(x),
+                Item::Attribute(x) => attributes.push(x),
+                Item::Generic(x) => generics.extend(x),
+                Item::Param(x) => params.push(x),
+                Item::Return(x) => returns.push(x),
+                Item::Overload(x) => overloads.push(x),
+                Item::See(x) => refs.push(x),
+                Item::Usage(x) => usage = Some(x),
+            }
+        }
+        let Attachments {
+            deprecated,
+            since,
+            version,
+            admonitions,
+        } = attached;
+
+        Self {
             op,
             prefix: Prefix {
                 left: Some(prefix.clone()),
                 right: Some(prefix),
             },
             desc,
+            deprecated,
+            since,
+            version,
+            admonitions,
+            attributes,
+            generics,
             params,
             returns,
-            see,
+            overloads,
+            see: See { refs },
             usage,
-        },
-    )
+        }
+    })
 });
 
 impl<T: Visitor> Accept<T> for Func {
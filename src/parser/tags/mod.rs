@@ -18,8 +18,15 @@ mod see;
 pub use see::*;
 mod usage;
 pub use usage::*;
+mod deprecated;
+pub use deprecated::*;
+mod admonition;
+pub use admonition::*;
+mod attached;
+pub(crate) use attached::*;
 
 #[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Prefix {
     pub left: Option<String>,
     pub right: Option<String>,
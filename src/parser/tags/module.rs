@@ -1,15 +1,34 @@
-use chumsky::select;
+use chumsky::{select, Parser};
 
-use crate::{lexer::TagType, parser::impl_parse, Accept, Visitor};
+use crate::{
+    lexer::TagType,
+    parser::{impl_parse, Admonition, Deprecated},
+    Accept, Visitor,
+};
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Module {
     pub name: String,
     pub desc: Option<String>,
+    pub deprecated: Option<Deprecated>,
+    pub admonitions: Vec<Admonition>,
 }
 
 impl_parse!(Module, {
-    select! { TagType::Module(name, desc) => Self { name, desc } }
+    select! { TagType::Module(name, desc) => (name, desc) }
+        .then(Admonition::parse().repeated())
+        .then(Deprecated::parse().or_not())
+        .then(Admonition::parse().repeated())
+        .map(|((((name, desc), mut admonitions), deprecated), rest)| {
+            admonitions.extend(rest);
+            Self {
+                name,
+                desc,
+                deprecated,
+                admonitions,
+            }
+        })
 });
 
 impl<T: Visitor> Accept<T> for Module {
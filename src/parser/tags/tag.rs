@@ -3,6 +3,7 @@ use chumsky::select;
 use crate::{lexer::TagType, parser::impl_parse, Accept, Visitor};
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Tag(pub String);
 
 impl_parse!(Tag, {
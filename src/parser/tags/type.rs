@@ -1,16 +1,21 @@
-use chumsky::{select, Parser};
+use chumsky::{prelude::choice, select, Parser};
 
 use crate::{
     lexer::{Op, TagType, Ty},
-    parser::{impl_parse, Prefix, See},
+    parser::{impl_parse, Admonition, Attached, Attachments, Deprecated, Prefix, See},
     Accept, Visitor,
 };
 
 use super::Usage;
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Type {
     pub desc: (Vec<String>, Option<String>),
+    pub deprecated: Option<Deprecated>,
+    pub since: Option<String>,
+    pub version: Option<String>,
+    pub admonitions: Vec<Admonition>,
     pub op: Op,
     pub prefix: Prefix,
     pub ty: Ty,
@@ -18,28 +23,64 @@ pub struct Type {
     pub usage: Option<Usage>,
 }
 
+/// Tags of a type which can be placed in any order after the `---@type`
+enum Item {
+    Attached(Attached),
+    See(String),
+    Usage(Usage),
+}
+
 impl_parse!(Type, {
     select! {
         TagType::Comment(x) => x
     }
     .repeated()
+    .then(Attached::parse().repeated())
     .then(select! { TagType::Type(ty, desc) => (ty, desc) })
-    .then(See::parse())
-    .then(Usage::parse().or_not())
+    .then(
+        choice((
+            Attached::parse().map(Item::Attached),
+            select! { TagType::See(x) => Item::See(x) },
+            Usage::parse().map(Item::Usage),
+        ))
+        .repeated(),
+    )
     .then(select! { TagType::Expr(prefix, op) => (prefix, op) })
-    .map(
-        |((((extract, (ty, desc)), see), usage), (prefix, op))| Self {
+    .map(|((((extract, before), (ty, desc)), items), (prefix, op))| {
+        let mut attached = Attachments::default();
+        attached.extend(before);
+        let mut refs = vec![];
+        let mut usage = None;
+        for item in items {
+            match item {
+                Item::Attached(x) => attached.push(x),
+                Item::See(x) => refs.push(x),
+                Item::Usage(x) => usage = Some(x),
+            }
+        }
+        let Attachments {
+            deprecated,
+            since,
+            version,
+            admonitions,
+        } = attached;
+
+        Self {
             desc: (extract, desc),
+            deprecated,
+            since,
+            version,
+            admonitions,
             prefix: Prefix {
                 left: Some(prefix.to_owned()),
                 right: Some(prefix),
             },
             op,
             ty,
-            see,
+            see: See { refs },
             usage,
-        },
-    )
+        }
+    })
 });
 
 impl<T: Visitor> Accept<T> for Type {
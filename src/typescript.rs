@@ -0,0 +1,459 @@
+use std::fmt::Display;
+
+use crate::{
+    lexer::{Generic, Member, Name, Op, Scope, Ty},
+    parser::{
+        Alias, AliasKind, Brief, Class, Deprecated, Divider, Field, Func, Module, Node, Param,
+        Return, See, Tag, Type, Usage,
+    },
+    Accept, FromEmmy, Settings, Visitor,
+};
+
+/// TypeScript declaration file (`.d.ts`) for TypeScriptToLua. Every `---@mod` becomes a
+/// `declare module` block, whereas classes and aliases are declared globally like in LuaLS.
+#[derive(Debug)]
+pub struct TypeScript(String);
+
+impl Visitor for TypeScript {
+    type R = String;
+    type S = Settings;
+
+    fn module(&self, _: &Module, _: &Self::S) -> Self::R {
+        String::new()
+    }
+
+    fn divider(&self, _: &Divider, _: &Self::S) -> Self::R {
+        String::new()
+    }
+
+    fn brief(&self, _: &Brief, _: &Self::S) -> Self::R {
+        String::new()
+    }
+
+    fn tag(&self, _: &Tag, _: &Self::S) -> Self::R {
+        String::new()
+    }
+
+    fn func(&self, n: &Func, s: &Self::S) -> Self::R {
+        let mut docs = n.desc.clone();
+        for param in &n.params {
+            if !param.desc.is_empty() {
+                docs.push(format!("@param {} {}", param.name, param.desc.join("\n")));
+            }
+        }
+        for entry in &n.returns {
+            if !entry.desc.is_empty() {
+                docs.push(format!("@returns {}", entry.desc.join("\n")));
+            }
+        }
+        docs.extend(n.see.refs.iter().map(|r| format!("@see {r}")));
+        docs.extend(deprecated(&n.deprecated));
+        docs.extend(n.since.iter().map(|v| format!("@since {v}")));
+
+        let (path, name, is_method) = split_op(&n.op);
+        let mut params = self.params(&n.params, s);
+        if !is_method {
+            // Functions called with `.` don't receive the `self`
+            params = if params.is_empty() {
+                "this: void".into()
+            } else {
+                format!("this: void, {params}")
+            };
+        }
+        let mut doc = jsdoc(&docs);
+        doc.push_str(&format!(
+            "export function {name}{}({params}): {};\n",
+            generics(&n.generics),
+            self.returns(&n.returns, s)
+        ));
+        namespaced(&path, doc)
+    }
+
+    fn params(&self, n: &[Param], _: &Self::S) -> Self::R {
+        n.iter()
+            .map(|p| match &p.name {
+                Name::Req(n) if n == "..." => format!("...args: {}[]", wrap(&p.ty)),
+                n => property(n, &p.ty),
+            })
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    fn r#returns(&self, n: &[Return], _: &Self::S) -> Self::R {
+        returns(n.iter().map(|r| &r.ty))
+    }
+
+    fn class(&self, n: &Class, s: &Self::S) -> Self::R {
+        let mut docs = n.desc.clone();
+        docs.extend(deprecated(&n.deprecated));
+        docs.extend(n.since.iter().map(|v| format!("@since {v}")));
+        global(&n.name, |name| {
+            let mut doc = jsdoc(&docs);
+            doc.push_str(&format!("interface {name}{}", generics(&n.generics)));
+            if !n.parents.is_empty() {
+                let parents = n.parents.iter().map(ty).collect::<Vec<String>>().join(", ");
+                doc.push_str(&format!(" extends {parents}"));
+            }
+            doc.push_str(" {\n");
+            doc.push_str(&indent(&self.fields(&n.fields, s)));
+            doc.push_str("}\n");
+            doc
+        })
+    }
+
+    fn fields(&self, n: &[Field], _: &Self::S) -> Self::R {
+        let mut doc = String::new();
+        for field in n {
+            if field.scope != Scope::Public {
+                continue;
+            }
+            let mut docs = field.desc.clone();
+            docs.extend(deprecated(&field.deprecated));
+            doc.push_str(&jsdoc(&docs));
+            doc.push_str(&property(&field.name, &field.ty));
+            doc.push_str(";\n");
+        }
+        doc
+    }
+
+    fn alias(&self, n: &Alias, _: &Self::S) -> Self::R {
+        let mut docs = n.desc.clone();
+        docs.extend(deprecated(&n.deprecated));
+        docs.extend(n.since.iter().map(|v| format!("@since {v}")));
+        let ty = match &n.kind {
+            AliasKind::Type(t) => ty(t),
+            AliasKind::Enum(variants) => variants
+                .iter()
+                .map(|(m, _)| member(m))
+                .collect::<Vec<String>>()
+                .join(" | "),
+            // Keys are used as it is, where bracketed ones are the same as their value
+            AliasKind::Table(true, fields) => fields
+                .iter()
+                .map(
+                    |(k, _, _)| match k.strip_prefix('[').and_then(|k| k.strip_suffix(']')) {
+                        Some(k) => literal(k.trim()),
+                        None => format!("\"{k}\""),
+                    },
+                )
+                .collect::<Vec<String>>()
+                .join(" | "),
+            // Only the literal values can be used as a type
+            AliasKind::Table(false, fields) => fields
+                .iter()
+                .map(|(_, v, _)| literal(v))
+                .collect::<Vec<String>>()
+                .join(" | "),
+        };
+        global(&n.name, |name| {
+            format!("{}type {name} = {ty};\n", jsdoc(&docs))
+        })
+    }
+
+    fn r#type(&self, n: &Type, _: &Self::S) -> Self::R {
+        let (extract, desc) = &n.desc;
+        let mut docs = extract.clone();
+        docs.extend(desc.clone());
+        docs.extend(n.see.refs.iter().map(|r| format!("@see {r}")));
+        docs.extend(deprecated(&n.deprecated));
+        docs.extend(n.since.iter().map(|v| format!("@since {v}")));
+
+        let (path, name, _) = split_op(&n.op);
+        let mut doc = jsdoc(&docs);
+        doc.push_str(&format!("export const {name}: {};\n", ty(&n.ty)));
+        namespaced(&path, doc)
+    }
+
+    fn see(&self, _: &See, _: &Self::S) -> Self::R {
+        String::new()
+    }
+
+    fn usage(&self, _: &Usage, _: &Self::S) -> Self::R {
+        String::new()
+    }
+
+    fn toc(&self, _: &str, _: &[Node], _: &Self::S) -> Self::R {
+        String::new()
+    }
+}
+
+impl FromEmmy for TypeScript {
+    type Settings = Settings;
+    fn from_emmy(t: &impl crate::Nodes, s: &Self::Settings) -> Self {
+        let shelf = Self(String::new());
+        let mut globals = String::new();
+        // (declaration, name, body) of every module or namespace in the order of appearance
+        let mut blocks: Vec<(&str, &str, String)> = vec![];
+        let mut module = None;
+        for node in t.nodes() {
+            let (decl, name) = match node {
+                Node::Module(x) => {
+                    module = Some(x.name.as_str());
+                    continue;
+                }
+                Node::Class(..) | Node::Alias(..) => {
+                    globals.push('\n');
+                    globals.push_str(&node.accept(&shelf, s));
+                    continue;
+                }
+                Node::Func(Func { prefix, .. }) | Node::Type(Type { prefix, .. }) => {
+                    match (module, prefix.left.as_deref()) {
+                        (Some(m), _) => ("declare module", m),
+                        // Without `---@mod` the exported table is used as a global namespace
+                        (None, Some(p)) => ("declare namespace", p),
+                        (None, None) => continue,
+                    }
+                }
+                _ => continue,
+            };
+            if !matches!(blocks.last(), Some((d, n, _)) if *d == decl && *n == name) {
+                blocks.push((decl, name, String::new()));
+            }
+            if let Some((.., body)) = blocks.last_mut() {
+                if !body.is_empty() {
+                    body.push('\n');
+                }
+                body.push_str(&node.accept(&shelf, s));
+            }
+        }
+
+        let mut doc = globals.trim_start().to_owned();
+        for (decl, name, body) in blocks {
+            if !doc.is_empty() {
+                doc.push('\n');
+            }
+            match decl {
+                "declare module" => doc.push_str(&format!("{decl} \"{name}\" {{\n")),
+                _ => doc.push_str(&format!("{decl} {name} {{\n")),
+            }
+            doc.push_str(&indent(&body));
+            doc.push_str("}\n");
+        }
+        Self(doc)
+    }
+}
+
+impl Display for TypeScript {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.0.as_str())
+    }
+}
+
+// #################
+
+/// Translates the lua type into its typescript counterpart
+fn ty(t: &Ty) -> String {
+    match t {
+        Ty::Nil => "undefined".into(),
+        Ty::Any => "any".into(),
+        Ty::Unknown => "unknown".into(),
+        Ty::Boolean => "boolean".into(),
+        Ty::String => "string".into(),
+        Ty::Number | Ty::Integer => "number".into(),
+        Ty::Function => "Function".into(),
+        Ty::Thread => "LuaThread".into(),
+        Ty::Userdata | Ty::Lightuserdata => "LuaUserdata".into(),
+        Ty::Ref(r) => r.to_owned(),
+        Ty::Member(m) => member(m),
+        Ty::Array(t) => format!("{}[]", wrap(t)),
+        Ty::Table(kv) => match kv {
+            Some((k, v)) => format!("LuaTable<{}, {}>", ty(k), ty(v)),
+            None => "LuaTable".into(),
+        },
+        Ty::Fun(args, ret) => {
+            let mut params = vec!["this: void".to_string()];
+            params.extend(args.iter().map(|(n, t)| match n {
+                Name::Req(n) if n == "..." => format!("...args: {}[]", wrap(t)),
+                n => property(n, t),
+            }));
+            let ret = match ret {
+                Some(ret) => returns(ret.iter().map(|(_, t)| t)),
+                None => "void".into(),
+            };
+            format!("({}) => {ret}", params.join(", "))
+        }
+        Ty::Dict(kv) => {
+            if kv.is_empty() {
+                return "{}".into();
+            }
+            let kv = kv
+                .iter()
+                .map(|(n, t)| property(n, t))
+                .collect::<Vec<String>>()
+                .join("; ");
+            format!("{{ {kv} }}")
+        }
+        Ty::Union(types) => types
+            .iter()
+            .map(|t| match t {
+                Ty::Fun(..) => wrap(t),
+                _ => ty(t),
+            })
+            .collect::<Vec<String>>()
+            .join(" | "),
+        Ty::Opt(t) => format!("{} | undefined", wrap(t)),
+        Ty::Tuple(items) => format!(
+            "[{}]",
+            items.iter().map(ty).collect::<Vec<String>>().join(", ")
+        ),
+        Ty::Num(num) => num.to_owned(),
+        Ty::Bool(b) => b.to_string(),
+        Ty::Apply(name, args) => format!(
+            "{name}<{}>",
+            args.iter().map(ty).collect::<Vec<String>>().join(", ")
+        ),
+    }
+}
+
+/// Type parameters along with their constraints i.e., `<T, U extends P>`
+#[inline]
+fn generics(g: &[Generic]) -> String {
+    if g.is_empty() {
+        return String::new();
+    }
+    let list = g
+        .iter()
+        .map(|g| match &g.parent {
+            Some(parent) => format!("{} extends {}", g.name, ty(parent)),
+            None => g.name.to_owned(),
+        })
+        .collect::<Vec<String>>()
+        .join(", ");
+    format!("<{list}>")
+}
+
+/// Property of an object or a parameter, where bracketed keys become an index signature
+#[inline]
+fn property(n: &Name, t: &Ty) -> String {
+    match (n, t) {
+        // `x: T?` is the same as `x?: T`
+        (Name::Req(n) | Name::Opt(n), Ty::Opt(t)) => format!("{n}?: {}", ty(t)),
+        (Name::Req(n), _) => format!("{n}: {}", ty(t)),
+        (Name::Opt(n), _) => format!("{n}?: {}", ty(t)),
+        (Name::Index(key @ (Ty::Member(Member::Literal(_)) | Ty::Num(_))), _) => {
+            format!("{}: {}", ty(key), ty(t))
+        }
+        (Name::Index(key), _) => format!("[key: {}]: {}", ty(key), ty(t)),
+    }
+}
+
+/// Parenthesize the types which can't be used as it is in an array i.e., `T[]`
+#[inline]
+fn wrap(t: &Ty) -> String {
+    match t {
+        Ty::Union(..) | Ty::Opt(..) | Ty::Fun(..) => format!("({})", ty(t)),
+        _ => ty(t),
+    }
+}
+
+/// Number or string literal of lua as it is, otherwise it's `unknown`
+#[inline]
+fn literal(v: &str) -> String {
+    match v.parse::<f64>() {
+        Ok(_) => v.to_owned(),
+        Err(_) if v.starts_with(['"', '\'']) => v.to_owned(),
+        Err(_) => "unknown".into(),
+    }
+}
+
+#[inline]
+fn member(m: &Member) -> String {
+    match m {
+        Member::Literal(_) => m.to_string(),
+        Member::Ident(i) => i.to_owned(),
+    }
+}
+
+/// Multiple return values are represented using TSTL's `LuaMultiReturn`
+#[inline]
+fn returns<'a>(ret: impl ExactSizeIterator<Item = &'a Ty>) -> String {
+    match ret.len() {
+        0 => "void".into(),
+        1 => ret.map(ty).collect(),
+        _ => format!(
+            "LuaMultiReturn<[{}]>",
+            ret.map(ty).collect::<Vec<String>>().join(", ")
+        ),
+    }
+}
+
+/// Splits the member access into (namespaces, name, is_method)
+#[inline]
+fn split_op(op: &Op) -> (Vec<String>, String, bool) {
+    let mut path = vec![];
+    let mut flat = vec![];
+    fn flatten<'a>(op: &'a Op, flat: &mut Vec<&'a Op>) {
+        match op {
+            Op::Deep(ops) => ops.iter().for_each(|o| flatten(o, flat)),
+            _ => flat.push(op),
+        }
+    }
+    flatten(op, &mut flat);
+    let last = flat.pop();
+    for o in flat {
+        if let Op::Dot(x) | Op::Colon(x) = o {
+            path.push(x.to_owned());
+        }
+    }
+    match last {
+        Some(Op::Colon(x)) => (path, x.to_owned(), true),
+        Some(Op::Dot(x)) => (path, x.to_owned(), false),
+        _ => (path, String::new(), false),
+    }
+}
+
+/// Dotted names like `my.mod.Base` aren't valid identifiers, so the global is declared
+/// inside the namespace instead
+#[inline]
+fn global(name: &str, decl: impl FnOnce(&str) -> String) -> String {
+    match name.rsplit_once('.') {
+        Some((ns, name)) => format!("declare namespace {ns} {{\n{}}}\n", indent(&decl(name))),
+        None => decl(name),
+    }
+}
+
+#[inline]
+fn namespaced(path: &[String], doc: String) -> String {
+    path.iter().rev().fold(doc, |doc, ns| {
+        format!("export namespace {ns} {{\n{}}}\n", indent(&doc))
+    })
+}
+
+/// `@deprecated` line of the JSDoc
+#[inline]
+fn deprecated(n: &Option<Deprecated>) -> Option<String> {
+    n.as_ref().map(|Deprecated { message }| match message {
+        Some(msg) => format!("@deprecated {msg}"),
+        None => String::from("@deprecated"),
+    })
+}
+
+#[inline]
+fn jsdoc(lines: &[String]) -> String {
+    match lines {
+        [] => String::new(),
+        [line] if !line.contains('\n') => format!("/** {line} */\n"),
+        _ => {
+            let mut doc = String::from("/**\n");
+            for line in lines.iter().flat_map(|l| l.lines()) {
+                if line.is_empty() {
+                    doc.push_str(" *\n");
+                } else {
+                    doc.push_str(&format!(" * {line}\n"));
+                }
+            }
+            doc.push_str(" */\n");
+            doc
+        }
+    }
+}
+
+#[inline]
+fn indent(doc: &str) -> String {
+    doc.lines()
+        .map(|l| match l {
+            "" => "\n".to_string(),
+            l => format!("    {l}\n"),
+        })
+        .collect()
+}
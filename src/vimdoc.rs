@@ -1,14 +1,11 @@
 use std::fmt::Display;
 
 use crate::{
-    lexer::{Name, Scope},
-    parser::{AliasKind, Divider, Module, Node},
+    lexer::{Generic, Name, Scope, Ty},
+    parser::{Admonition, AliasKind, Deprecated, Divider, Module, Node},
     Accept, FromEmmy, Layout, Settings, Visitor,
 };
 
-/// Text Width
-const TW: usize = 80;
-
 #[derive(Debug)]
 pub struct VimDoc(String);
 
@@ -24,15 +21,24 @@ impl Visitor for VimDoc {
         doc.push_str(&format!(
             "{:>w$}",
             format!("*{}*", n.name),
-            w = TW - desc.len()
+            w = s.text_width.saturating_sub(desc.len())
         ));
         doc.push('\n');
+        if n.deprecated.is_some() {
+            doc.push('\n');
+            doc.push_str(&deprecated(&n.deprecated, s));
+        }
+        if !n.admonitions.is_empty() {
+            doc.push('\n');
+            doc.push_str(admonitions(&n.admonitions, s).trim_end_matches('\n'));
+            doc.push('\n');
+        }
         doc
     }
 
-    fn divider(&self, n: &crate::parser::Divider, _: &Self::S) -> Self::R {
-        let mut doc = String::with_capacity(TW - 1);
-        for _ in 0..TW - 2 {
+    fn divider(&self, n: &crate::parser::Divider, s: &Self::S) -> Self::R {
+        let mut doc = String::with_capacity(s.text_width);
+        for _ in 0..s.text_width.saturating_sub(2) {
             doc.push(n.0);
         }
         doc.push('\n');
@@ -45,8 +51,8 @@ impl Visitor for VimDoc {
         doc
     }
 
-    fn tag(&self, n: &crate::parser::Tag, _: &Self::S) -> Self::R {
-        format!("{:>w$}", format!("*{}*", n.0), w = TW)
+    fn tag(&self, n: &crate::parser::Tag, s: &Self::S) -> Self::R {
+        format!("{:>w$}", format!("*{}*", n.0), w = s.text_width)
     }
 
     fn func(&self, n: &crate::parser::Func, s: &Self::S) -> Self::R {
@@ -59,21 +65,78 @@ impl Visitor for VimDoc {
                 .collect::<Vec<String>>()
                 .join(", ");
             format!(
-                "{}{}({args})",
+                "{}{}{}({args})",
                 n.prefix.left.as_deref().unwrap_or_default(),
-                n.op
+                n.op,
+                Generic::angled(&n.generics)
             )
         } else {
-            format!("{}{}()", n.prefix.left.as_deref().unwrap_or_default(), n.op)
+            format!(
+                "{}{}{}()",
+                n.prefix.left.as_deref().unwrap_or_default(),
+                n.op,
+                Generic::angled(&n.generics)
+            )
         };
         doc.push_str(&header(
             &name_with_param,
             &format!("{}{}", n.prefix.right.as_deref().unwrap_or_default(), n.op),
+            s.text_width,
         ));
+        for overload in &n.overloads {
+            let overload = match s.expand_opt {
+                true => overload.expand_opt(),
+                false => overload.to_owned(),
+            };
+            // Signature along with the return types i.e., `U.open({opts}): Handle`, whereas
+            // anything other than a function is shown as the type itself
+            let signature = match overload {
+                Ty::Fun(args, ret) => {
+                    let args = args
+                        .iter()
+                        .map(|(name, _)| format!("{{{name}}}"))
+                        .collect::<Vec<String>>()
+                        .join(", ");
+                    let ret = ret
+                        .iter()
+                        .flatten()
+                        .map(|(_, ty)| ty.to_string())
+                        .collect::<Vec<String>>()
+                        .join(", ");
+                    match ret.is_empty() {
+                        true => format!("{}({args})", Generic::angled(&n.generics)),
+                        false => format!("{}({args}): {ret}", Generic::angled(&n.generics)),
+                    }
+                }
+                ty => format!(": {ty}"),
+            };
+            doc.push_str(&format!(
+                "{}{}{signature}\n",
+                n.prefix.left.as_deref().unwrap_or_default(),
+                n.op,
+            ));
+        }
         if !n.desc.is_empty() {
-            doc.push_str(&description(&n.desc.join("\n"), s.indent_width))
+            doc.push_str(&description(&paragraphs(&n.desc, s), s.indent_width))
         }
         doc.push('\n');
+        if n.deprecated.is_some() {
+            doc.push_str(&deprecated(&n.deprecated, s));
+            doc.push('\n');
+        }
+        doc.push_str(&versions(&n.since, &n.version, s));
+        doc.push_str(&admonitions(&n.admonitions, s));
+        if !n.attributes.is_empty() {
+            let attrs = n
+                .attributes
+                .iter()
+                .map(|a| a.to_string())
+                .collect::<Vec<String>>()
+                .join(", ");
+            doc.push_str(&description("Attributes: ~", s.indent_width));
+            doc.push_str(&description(&attrs, s.indent_width * 2));
+            doc.push('\n');
+        }
         if !n.params.is_empty() {
             doc.push_str(&description("Parameters: ~", s.indent_width));
             doc.push_str(&self.params(&n.params, s));
@@ -94,10 +157,17 @@ impl Visitor for VimDoc {
     }
 
     fn params(&self, n: &[crate::parser::Param], s: &Self::S) -> Self::R {
-        let mut table = Table::new(s.indent_width);
+        let mut table = Table::new(s).hang(match s.layout {
+            Layout::Default => 0,
+            Layout::Compact(n) | Layout::Mini(n) => n,
+        });
         for param in n {
             let (name, ty) = match (s.expand_opt, &param.name) {
-                (true, Name::Opt(n)) => (format!("{{{n}}}"), format!("(nil|{})", param.ty)),
+                (true, Name::Opt(n)) => (
+                    format!("{{{n}}}"),
+                    format!("({})", Ty::union([Ty::Nil, param.ty.expand_opt()])),
+                ),
+                (true, n) => (format!("{{{n}}}"), format!("({})", param.ty.expand_opt())),
                 (_, n) => (format!("{{{n}}}"), format!("({})", param.ty)),
             };
             match s.layout {
@@ -125,12 +195,18 @@ impl Visitor for VimDoc {
     }
 
     fn r#returns(&self, n: &[crate::parser::Return], s: &Self::S) -> Self::R {
-        let mut table = Table::new(s.indent_width);
+        let mut table = Table::new(s).hang(match s.layout {
+            Layout::Mini(n) => n,
+            _ => 0,
+        });
         for entry in n {
+            let ty = match s.expand_opt {
+                true => entry.ty.expand_opt(),
+                false => entry.ty.clone(),
+            };
             if let Layout::Mini(n) = s.layout {
                 table.add_row([format!(
-                    "({}) {}",
-                    entry.ty,
+                    "({ty}) {}",
                     if entry.desc.is_empty() {
                         entry.name.clone().unwrap_or_default()
                     } else {
@@ -139,7 +215,7 @@ impl Visitor for VimDoc {
                 )]);
             } else {
                 table.add_row([
-                    format!("({})", entry.ty),
+                    format!("({ty})"),
                     if entry.desc.is_empty() {
                         entry.name.clone().unwrap_or_default()
                     } else {
@@ -154,26 +230,72 @@ impl Visitor for VimDoc {
     fn class(&self, n: &crate::parser::Class, s: &Self::S) -> Self::R {
         let mut doc = String::new();
         let name = format!(
-            "{}{}",
+            "{}{}{}",
             n.name,
-            n.parent
-                .as_ref()
-                .map_or(String::new(), |parent| format!(" : {parent}"))
+            Generic::angled(&n.generics),
+            if n.parents.is_empty() {
+                String::new()
+            } else {
+                let parents = n
+                    .parents
+                    .iter()
+                    .map(|p| match p {
+                        Ty::Ref(name) => format!("|{name}|"),
+                        Ty::Apply(name, args) => format!(
+                            "|{name}|<{}>",
+                            args.iter()
+                                .map(|a| a.to_string())
+                                .collect::<Vec<String>>()
+                                .join(",")
+                        ),
+                        p => p.to_string(),
+                    })
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                format!(" : {parents}")
+            }
         );
         if let Some(prefix) = &n.prefix.right {
-            doc.push_str(&header(&name, &format!("{prefix}.{}", n.name)));
+            doc.push_str(&header(
+                &name,
+                &format!("{prefix}.{}", n.name),
+                s.text_width,
+            ));
         } else {
-            doc.push_str(&header(&name, &n.name));
+            doc.push_str(&header(&name, &n.name, s.text_width));
         }
         if !n.desc.is_empty() {
-            doc.push_str(&description(&n.desc.join("\n"), s.indent_width));
+            doc.push_str(&description(&paragraphs(&n.desc, s), s.indent_width));
         }
         doc.push('\n');
+        if n.deprecated.is_some() {
+            doc.push_str(&deprecated(&n.deprecated, s));
+            doc.push('\n');
+        }
+        doc.push_str(&versions(&n.since, &n.version, s));
+        doc.push_str(&admonitions(&n.admonitions, s));
         if !n.fields.is_empty() {
             doc.push_str(&description("Fields: ~", s.indent_width));
             doc.push_str(&self.fields(&n.fields, s));
             doc.push('\n');
         }
+        if !n.operators.is_empty() {
+            doc.push_str(&description("Operators: ~", s.indent_width));
+            let mut table = Table::new(s);
+            for op in &n.operators {
+                let expand = |t: &Ty| match s.expand_opt {
+                    true => t.expand_opt(),
+                    false => t.to_owned(),
+                };
+                let kind = match &op.operand {
+                    Some(operand) => format!("{}({})", op.kind, expand(operand)),
+                    None => op.kind.to_owned(),
+                };
+                table.add_row([kind, format!("({})", expand(&op.result))]);
+            }
+            doc.push_str(&table.to_string());
+            doc.push('\n');
+        }
         if !n.see.refs.is_empty() {
             doc.push_str(&self.see(&n.see, s));
         }
@@ -181,30 +303,44 @@ impl Visitor for VimDoc {
     }
 
     fn fields(&self, n: &[crate::parser::Field], s: &Self::S) -> Self::R {
-        let mut table = Table::new(s.indent_width);
+        let mut table = Table::new(s).hang(match s.layout {
+            Layout::Default => 0,
+            Layout::Compact(n) | Layout::Mini(n) => n,
+        });
         for field in n {
             let (name, ty) = match (s.expand_opt, &field.name) {
-                (true, Name::Opt(n)) => (format!("{{{n}}}"), format!("(nil|{})", field.ty)),
-                (_, n) => (format!("{{{n}}}"), format!("({})", field.ty)),
+                (true, Name::Opt(n)) => (
+                    format!("{{{n}}}"),
+                    format!("({})", Ty::union([Ty::Nil, field.ty.expand_opt()])),
+                ),
+                (true, n) => (key(n), format!("({})", field.ty.expand_opt())),
+                (_, n) => (key(n), format!("({})", field.ty)),
             };
             if field.scope == Scope::Public {
+                let mut desc = field.desc.clone();
+                if let Some(Deprecated { message }) = &field.deprecated {
+                    match message {
+                        Some(msg) => desc.insert(0, format!("Deprecated: {msg}")),
+                        None => desc.insert(0, "Deprecated".into()),
+                    }
+                }
                 match s.layout {
                     Layout::Default => {
-                        table.add_row([name, ty, field.desc.join("\n")]);
+                        table.add_row([name, ty, desc.join("\n")]);
                     }
                     Layout::Compact(n) => {
                         table.add_row([
                             name,
                             format!(
                                 "{ty} {}",
-                                field.desc.join(&format!("\n{}", " ".repeat(n as usize)))
+                                desc.join(&format!("\n{}", " ".repeat(n as usize)))
                             ),
                         ]);
                     }
                     Layout::Mini(n) => {
                         table.add_row([format!(
                             "{name} {ty} {}",
-                            field.desc.join(&format!("\n{}", " ".repeat(n as usize)))
+                            desc.join(&format!("\n{}", " ".repeat(n as usize)))
                         )]);
                     }
                 };
@@ -216,29 +352,50 @@ impl Visitor for VimDoc {
     fn alias(&self, n: &crate::parser::Alias, s: &Self::S) -> Self::R {
         let mut doc = String::new();
         if let Some(prefix) = &n.prefix.right {
-            doc.push_str(&header(&n.name, &format!("{prefix}.{}", n.name)));
+            doc.push_str(&header(
+                &n.name,
+                &format!("{prefix}.{}", n.name),
+                s.text_width,
+            ));
         } else {
-            doc.push_str(&header(&n.name, &n.name));
+            doc.push_str(&header(&n.name, &n.name, s.text_width));
         }
         if !n.desc.is_empty() {
-            doc.push_str(&description(&n.desc.join("\n"), s.indent_width));
+            doc.push_str(&description(&paragraphs(&n.desc, s), s.indent_width));
         }
         doc.push('\n');
+        if n.deprecated.is_some() {
+            doc.push_str(&deprecated(&n.deprecated, s));
+            doc.push('\n');
+        }
+        doc.push_str(&versions(&n.since, &n.version, s));
+        doc.push_str(&admonitions(&n.admonitions, s));
         match &n.kind {
             AliasKind::Type(ty) => {
                 doc.push_str(&description("Type: ~", s.indent_width));
                 doc.push_str(&(" ").repeat(s.indent_width * 2));
-                doc.push_str(&ty.to_string());
+                match s.expand_opt {
+                    true => doc.push_str(&ty.expand_opt().to_string()),
+                    false => doc.push_str(&ty.to_string()),
+                }
                 doc.push('\n');
             }
             AliasKind::Enum(variants) => {
                 doc.push_str(&description("Variants: ~", s.indent_width));
-                let mut table = Table::new(s.indent_width);
+                let mut table = Table::new(s);
                 for (ty, desc) in variants {
                     table.add_row([&format!("({})", ty), desc.as_deref().unwrap_or_default()]);
                 }
                 doc.push_str(&table.to_string());
             }
+            AliasKind::Table(_, fields) => {
+                doc.push_str(&description("Variants: ~", s.indent_width));
+                let mut table = Table::new(s);
+                for (key, value, desc) in fields {
+                    table.add_row([key, &format!("({value})"), &desc.join("\n")]);
+                }
+                doc.push_str(&table.to_string());
+            }
         }
         doc.push('\n');
         doc
@@ -249,15 +406,26 @@ impl Visitor for VimDoc {
         doc.push_str(&header(
             &format!("{}{}", n.prefix.left.as_deref().unwrap_or_default(), n.op),
             &format!("{}{}", n.prefix.right.as_deref().unwrap_or_default(), n.op),
+            s.text_width,
         ));
         let (extract, desc) = &n.desc;
         if !extract.is_empty() {
-            doc.push_str(&description(&extract.join("\n"), s.indent_width));
+            doc.push_str(&description(&paragraphs(extract, s), s.indent_width));
         }
         doc.push('\n');
+        if n.deprecated.is_some() {
+            doc.push_str(&deprecated(&n.deprecated, s));
+            doc.push('\n');
+        }
+        doc.push_str(&versions(&n.since, &n.version, s));
+        doc.push_str(&admonitions(&n.admonitions, s));
         doc.push_str(&description("Type: ~", s.indent_width));
-        let mut table = Table::new(s.indent_width);
-        table.add_row([&format!("({})", n.ty), desc.as_deref().unwrap_or_default()]);
+        let ty = match s.expand_opt {
+            true => n.ty.expand_opt(),
+            false => n.ty.clone(),
+        };
+        let mut table = Table::new(s);
+        table.add_row([&format!("({ty})"), desc.as_deref().unwrap_or_default()]);
         doc.push_str(&table.to_string());
         doc.push('\n');
         if !n.see.refs.is_empty() {
@@ -300,6 +468,8 @@ impl Visitor for VimDoc {
             &Module {
                 name: n.to_string(),
                 desc: Some("Table of Contents".into()),
+                deprecated: None,
+                admonitions: vec![],
             },
             s,
         );
@@ -307,11 +477,14 @@ impl Visitor for VimDoc {
         doc.push('\n');
         for nod in nodes {
             if let Node::Module(x) = nod {
-                let desc = x.desc.as_deref().unwrap_or_default();
+                let mut desc = x.desc.clone().unwrap_or_default();
+                if x.deprecated.is_some() {
+                    desc.push_str(" (deprecated)");
+                }
                 doc.push_str(&format!(
                     "{desc} {:·>w$}\n",
                     format!(" |{}|", x.name),
-                    w = (TW - 1) - desc.len()
+                    w = s.text_width.saturating_sub(desc.len() + 1)
                 ));
             }
         }
@@ -332,10 +505,113 @@ impl FromEmmy for VimDoc {
             }
             shelf.0.push('\n');
         }
+        if s.changelog {
+            shelf.0.push_str(&shelf.changelog(nodes, s));
+        }
         shelf
     }
 }
 
+impl VimDoc {
+    /// Section which lists the tag of every symbol grouped by its `---@since` version
+    fn changelog(&self, nodes: &[Node], s: &Settings) -> String {
+        let mut versions: Vec<(&str, Vec<String>)> = vec![];
+        for node in nodes {
+            let (since, tag) = match node {
+                Node::Func(x) => (
+                    &x.since,
+                    format!("{}{}", x.prefix.right.as_deref().unwrap_or_default(), x.op),
+                ),
+                Node::Type(x) => (
+                    &x.since,
+                    format!("{}{}", x.prefix.right.as_deref().unwrap_or_default(), x.op),
+                ),
+                Node::Class(x) => (
+                    &x.since,
+                    x.prefix
+                        .right
+                        .as_ref()
+                        .map_or(x.name.to_owned(), |p| format!("{p}.{}", x.name)),
+                ),
+                Node::Alias(x) => (
+                    &x.since,
+                    x.prefix
+                        .right
+                        .as_ref()
+                        .map_or(x.name.to_owned(), |p| format!("{p}.{}", x.name)),
+                ),
+                _ => continue,
+            };
+            let Some(since) = since else {
+                continue;
+            };
+            match versions.iter_mut().find(|(v, _)| v == since) {
+                Some((_, tags)) => tags.push(tag),
+                None => versions.push((since, vec![tag])),
+            }
+        }
+        // Numeric parts are compared so that `0.10.0` comes after `0.9.0`
+        versions.sort_by_key(|(v, _)| {
+            v.split(|c: char| !c.is_ascii_digit())
+                .filter_map(|x| x.parse::<u64>().ok())
+                .collect::<Vec<u64>>()
+        });
+
+        let name = match nodes.iter().find(|x| matches!(x, Node::Module(_))) {
+            Some(Node::Module(m)) => format!("{}.changelog", m.name),
+            _ => "changelog".into(),
+        };
+        let mut doc = self.module(
+            &Module {
+                name,
+                desc: Some("Changelog by version".into()),
+                deprecated: None,
+                admonitions: vec![],
+            },
+            s,
+        );
+        for (version, tags) in versions {
+            doc.push('\n');
+            doc.push_str(&format!("{version} ~\n"));
+            for tag in tags {
+                doc.push_str(&description(&format!("|{tag}|"), s.indent_width));
+            }
+        }
+        doc.push('\n');
+        doc
+    }
+
+    /// Collects every `*tag*` from the help text, sorted, the same way `:helptags` does.
+    /// If a tag is defined more than once then the duplicate is returned as an error.
+    pub fn tags(&self) -> Result<Vec<&str>, &str> {
+        let mut tags = vec![];
+        for line in self.0.lines() {
+            let mut rest = line;
+            while let Some(start) = rest.find('*') {
+                let preceded = start == 0 || rest[..start].ends_with([' ', '\t']);
+                let after = &rest[start + 1..];
+                let Some(end) = after.find('*') else {
+                    break;
+                };
+                let tag = &after[..end];
+                let followed = after[end + 1..]
+                    .chars()
+                    .next()
+                    .map_or(true, |c| c == ' ' || c == '\t');
+                if preceded && followed && !tag.is_empty() && !tag.contains([' ', '\t', '|']) {
+                    tags.push(tag);
+                }
+                rest = &after[end..];
+            }
+        }
+        tags.sort_unstable();
+        if let Some(dup) = tags.windows(2).find(|w| w[0] == w[1]) {
+            return Err(dup[0]);
+        }
+        Ok(tags)
+    }
+}
+
 impl Display for VimDoc {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(self.0.as_str())
@@ -344,27 +620,74 @@ impl Display for VimDoc {
 
 // #################
 
-struct Table(comfy_table::Table, usize);
+struct Table {
+    rows: Vec<Vec<String>>,
+    indent: usize,
+    /// Text width to reflow the last column into, if wrapping is enabled
+    width: Option<usize>,
+    /// Indent of the wrapped lines of the last column
+    hang: usize,
+}
 
 impl Table {
-    pub fn new(ident: usize) -> Self {
-        let mut tbl = comfy_table::Table::new();
-        tbl.load_preset(comfy_table::presets::NOTHING);
-        // tbl.column_iter_mut().map(|c| c.set_padding((0, 0)));
-        Self(tbl, ident)
+    pub fn new(s: &Settings) -> Self {
+        Self {
+            rows: vec![],
+            indent: s.indent_width,
+            width: s.wrap.then_some(s.text_width),
+            hang: 0,
+        }
+    }
+
+    pub fn hang(mut self, n: u8) -> Self {
+        self.hang = n as usize;
+        self
     }
 
-    pub fn add_row<T: Into<comfy_table::Row>>(&mut self, row: T) -> &Self {
-        self.0.add_row(row);
+    pub fn add_row<T: ToString, const N: usize>(&mut self, row: [T; N]) -> &Self {
+        self.rows
+            .push(row.iter().map(ToString::to_string).collect());
         self
     }
 }
 
 impl std::fmt::Display for Table {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut tbl = comfy_table::Table::new();
+        tbl.load_preset(comfy_table::presets::NOTHING);
+
+        // Space taken by the indent and every column, along with its padding, except the last one
+        let cols = self.rows.iter().map(Vec::len).max().unwrap_or_default();
+        let offset = (0..cols.saturating_sub(1))
+            .map(|i| {
+                self.rows
+                    .iter()
+                    .filter_map(|row| row.get(i))
+                    .flat_map(|cell| cell.lines())
+                    .map(|line| line.chars().count())
+                    .max()
+                    .unwrap_or_default()
+                    + 2
+            })
+            .sum::<usize>()
+            + self.indent * 2;
+
+        for row in &self.rows {
+            match (self.width, row.split_last()) {
+                (Some(width), Some((last, rest))) => {
+                    let mut row = rest.to_vec();
+                    row.push(reflow(last, width.saturating_sub(offset), self.hang));
+                    tbl.add_row(row);
+                }
+                _ => {
+                    tbl.add_row(row);
+                }
+            }
+        }
+
         f.write_str(&textwrap::indent(
-            &self.0.trim_fmt(),
-            &(" ").repeat((self.1 * 2) - 1),
+            &tbl.trim_fmt(),
+            &(" ").repeat((self.indent * 2) - 1),
         ))?;
         f.write_str("\n")
     }
@@ -377,11 +700,174 @@ fn description(desc: &str, indent: usize) -> String {
     d
 }
 
+/// Field name wrapped in `{}`, except the index signature i.e., `[string]`
+#[inline]
+fn key(n: &Name) -> String {
+    match n {
+        Name::Index(_) => n.to_string(),
+        n => format!("{{{n}}}"),
+    }
+}
+
 #[inline]
-fn header(name: &str, tag: &str) -> String {
+fn header(name: &str, tag: &str, tw: usize) -> String {
     let len = name.len();
-    if len > 40 || tag.len() > 40 {
-        return format!("{:>w$}\n{}\n", format!("*{}*", tag), name, w = TW);
+    if len > tw / 2 || tag.len() > tw / 2 {
+        return format!("{:>w$}\n{}\n", format!("*{}*", tag), name, w = tw);
+    }
+    format!("{}{:>w$}\n", name, format!("*{}*", tag), w = tw - len)
+}
+
+/// Joins the lines of the description, which are reflowed to fit in the text width if
+/// wrapping is enabled
+#[inline]
+fn paragraphs(desc: &[String], s: &Settings) -> String {
+    let text = desc.join("\n");
+    if s.wrap {
+        reflow(&text, s.text_width.saturating_sub(s.indent_width), 0)
+    } else {
+        text
+    }
+}
+
+/// Minimum width of the text to be reflowed
+const MIN_WIDTH: usize = 20;
+
+/// Reflows every paragraph of the text to fit in the given width, where `hang` is the indent
+/// of all the lines except the first one. Blank lines and code blocks i.e., `>` ... `<` are
+/// kept as it is, whereas indented lines and list items i.e., `-`, `*` or `1.` start a new
+/// paragraph with their own indent.
+fn reflow(text: &str, width: usize, hang: usize) -> String {
+    // Too narrow to be readable i.e., a long field name in the table, so it's left as it is
+    if width < MIN_WIDTH {
+        return text.to_owned();
+    }
+    let hang = " ".repeat(hang);
+    let mut lines: Vec<String> = vec![];
+    let mut para = String::new();
+    // Indent of the first and the rest of the lines of the paragraph
+    let mut lead = (String::new(), String::new());
+    let mut code = false;
+
+    let flush = |para: &mut String, lines: &mut Vec<String>, lead: &(String, String)| {
+        if para.is_empty() {
+            return;
+        }
+        let first = format!("{}{}", if lines.is_empty() { "" } else { &hang }, lead.0);
+        let rest = format!("{hang}{}", lead.1);
+        let opts = textwrap::Options::new(width)
+            .break_words(false)
+            .initial_indent(&first)
+            .subsequent_indent(&rest);
+        lines.extend(textwrap::wrap(para, opts).into_iter().map(String::from));
+        para.clear();
+    };
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if code {
+            code = !trimmed.starts_with('<');
+            lines.push(line.to_owned());
+        } else if trimmed.is_empty() {
+            flush(&mut para, &mut lines, &lead);
+            lines.push(String::new());
+        } else if let Some(marker) = code_marker(trimmed) {
+            if marker == trimmed {
+                flush(&mut para, &mut lines, &lead);
+                lines.push(line.to_owned());
+            } else {
+                // Text before the `>` is still part of the paragraph
+                if !para.is_empty() {
+                    para.push(' ');
+                }
+                para.push_str(trimmed);
+                flush(&mut para, &mut lines, &lead);
+            }
+            code = true;
+        } else if line.starts_with(char::is_whitespace) || list_marker(trimmed).is_some() {
+            flush(&mut para, &mut lines, &lead);
+            let indent = &line[..line.len() - line.trim_start().len()];
+            let marker = list_marker(trimmed).map_or(0, |m| m.chars().count() + 1);
+            lead = (indent.to_owned(), format!("{indent}{}", " ".repeat(marker)));
+            para.push_str(trimmed);
+        } else {
+            if para.is_empty() {
+                lead = (String::new(), String::new());
+            } else {
+                para.push(' ');
+            }
+            para.push_str(trimmed);
+        }
+    }
+    flush(&mut para, &mut lines, &lead);
+
+    lines.join("\n")
+}
+
+/// Returns the `-`, `*` or `1.` at the start of the line which marks a list item
+#[inline]
+fn list_marker(line: &str) -> Option<&str> {
+    let (marker, _) = line.split_once(' ')?;
+    match marker {
+        "-" | "*" => Some(marker),
+        _ => marker
+            .strip_suffix('.')
+            .filter(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+            .map(|_| marker),
+    }
+}
+
+/// Returns the `>` or `>lang` at the end of the line which starts a code block
+#[inline]
+fn code_marker(line: &str) -> Option<&str> {
+    let marker = line.rsplit(' ').next()?;
+    let lang = marker.strip_prefix('>')?;
+    lang.chars().all(char::is_alphanumeric).then_some(marker)
+}
+
+/// `---@since` release and `---@version` lua versions of the item
+#[inline]
+fn versions(since: &Option<String>, version: &Option<String>, s: &Settings) -> String {
+    let mut doc = String::new();
+    if let Some(since) = since {
+        doc.push_str(&description("Since: ~", s.indent_width));
+        doc.push_str(&description(since, s.indent_width * 2));
+        doc.push('\n');
+    }
+    if let Some(version) = version {
+        doc.push_str(&description("Lua version: ~", s.indent_width));
+        doc.push_str(&description(version, s.indent_width * 2));
+        doc.push('\n');
+    }
+    doc
+}
+
+/// `Note: ~` and `Warning: ~` sections with the body indented under them
+#[inline]
+fn admonitions(n: &[Admonition], s: &Settings) -> String {
+    let mut doc = String::new();
+    for admonition in n {
+        doc.push_str(&description(
+            &format!("{}: ~", admonition.kind),
+            s.indent_width,
+        ));
+        doc.push_str(&description(
+            &paragraphs(&admonition.desc, s),
+            s.indent_width * 2,
+        ));
+        doc.push('\n');
+    }
+    doc
+}
+
+/// Prominent section for the `---@deprecated` items
+#[inline]
+fn deprecated(n: &Option<Deprecated>, s: &Settings) -> String {
+    let mut doc = description("Deprecated: ~", s.indent_width);
+    if let Some(Deprecated { message: Some(msg) }) = n {
+        doc.push_str(&(" ").repeat(s.indent_width * 2));
+        doc.push_str(msg);
+        doc.push('\n');
     }
-    format!("{}{:>w$}\n", name, format!("*{}*", tag), w = TW - len)
+    doc
 }
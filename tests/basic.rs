@@ -167,7 +167,7 @@ CommentConfig                                                    *CommentConfig*
         {sticky}     (boolean)                     Whether the cursor should stay at its position
                                                    NOTE: This only affects NORMAL mode mappings and doesn't work with dot-repeat
 
-        {ignore}     (string|fun():string)         Lines to be ignored while comment/uncomment.
+        {ignore}     (string|(fun():string))       Lines to be ignored while comment/uncomment.
                                                    Could be a regex string or a function that returns a regex string.
                                                    Example: Use '^$' to ignore empty lines
 
@@ -175,7 +175,7 @@ CommentConfig                                                    *CommentConfig*
         {post_hook}  (fun(ctx:CommentCtx))         Function to be called after comment/uncomment
 
 
-XMen : Homosapien                                                         *XMen*
+XMen : |Homosapien|                                                       *XMen*
 
     Fields: ~
         {power}  (number)  Power quantifier
@@ -659,6 +659,781 @@ U.VMODE                                                                *U.VMODE*
     )
 }
 
+#[test]
+fn generics() {
+    let src = r#"
+    local U = {}
+
+    ---Last in, first out
+    ---@class Stack<T>
+    ---@field items T[]
+
+    ---Maps over the list
+    ---@generic T, K : string
+    ---@param list T[]
+    ---@param fn fun(item: T): K
+    ---@return K[]
+    function U.map(list, fn)
+        return vim.tbl_map(fn, list)
+    end
+
+    ---Creates an empty stack
+    ---@generic T
+    ---@return Stack<T>
+    function U.stack()
+        return {}
+    end
+
+    return U
+    "#;
+
+    assert_eq!(
+        lemmy!(src),
+        "\
+Stack<T>                                                                 *Stack*
+    Last in, first out
+
+    Fields: ~
+        {items}  (T[])
+
+
+U.map<T, K : string>({list}, {fn})                                       *U.map*
+    Maps over the list
+
+    Parameters: ~
+        {list}  (T[])
+        {fn}    (fun(item:T):K)
+
+    Returns: ~
+        (K[])
+
+
+U.stack<T>()                                                           *U.stack*
+    Creates an empty stack
+
+    Returns: ~
+        (Stack<T>)
+
+
+"
+    );
+}
+
+#[test]
+fn overloads() {
+    let src = r#"
+    local U = {}
+
+    ---Opens a file
+    ---@param path string
+    ---@param mode? string
+    ---@return Handle
+    ---@overload fun(opts: table): Handle
+    ---@overload fun(path: string, ...: any)
+    ---@overload fun(fd: integer): boolean, string?
+    function U.open(path, mode)
+    end
+
+    return U
+    "#;
+
+    assert_eq!(
+        lemmy!(src),
+        "\
+U.open({path}, {mode?})                                                 *U.open*
+U.open({opts}): Handle
+U.open({path}, {...})
+U.open({fd}): boolean, string?
+    Opens a file
+
+    Parameters: ~
+        {path}   (string)
+        {mode?}  (string)
+
+    Returns: ~
+        (Handle)
+
+
+"
+    );
+}
+
+#[test]
+fn overloads_before_desc() {
+    let src = r#"
+    local U = {}
+
+    ---@overload fun(fd: integer)
+    ---Closes the file
+    ---@param path string
+    function U.close(path)
+    end
+
+    return U
+    "#;
+
+    assert_eq!(
+        lemmy!(src),
+        "\
+U.close({path})                                                        *U.close*
+U.close({fd})
+    Closes the file
+
+    Parameters: ~
+        {path}  (string)
+
+
+"
+    );
+}
+
+#[test]
+fn attributes() {
+    let src = r#"
+    local U = {}
+
+    ---Reads the file
+    ---@async
+    ---@nodiscard
+    ---@param path string
+    ---@return string
+    function U.read(path)
+    end
+
+    return U
+    "#;
+
+    assert_eq!(
+        lemmy!(src),
+        "\
+U.read({path})                                                          *U.read*
+    Reads the file
+
+    Attributes: ~
+        async, nodiscard
+
+    Parameters: ~
+        {path}  (string)
+
+    Returns: ~
+        (string)
+
+
+"
+    );
+}
+
+#[test]
+fn enum_table() {
+    let src = r#"
+    local U = {}
+
+    ---Vim modes
+    ---@enum Mode
+    local Mode = {
+        ---Normal mode
+        NORMAL = 1,
+        ---Visual mode
+        ---and selection
+        VISUAL = 2,
+        INSERT = "i", -- Insert mode
+        SEP = ',';
+        LAST = -1 -- Last one
+    }
+
+    ---@param mode Mode
+    function U.set(mode) end
+
+    return U
+    "#;
+
+    assert_eq!(
+        lemmy!(src),
+        "\
+Mode                                                                      *Mode*
+    Vim modes
+
+    Variants: ~
+        NORMAL  (1)    Normal mode
+        VISUAL  (2)    Visual mode
+                       and selection
+        INSERT  (\"i\")  Insert mode
+        SEP     (',')
+        LAST    (-1)   Last one
+
+
+U.set({mode})                                                            *U.set*
+
+    Parameters: ~
+        {mode}  (Mode)
+
+
+"
+    );
+}
+
+#[test]
+fn enum_table_keys() {
+    let src = r#"
+    local U = {}
+
+    ---@enum Color
+    local Color = {
+        ["dark-red"] = 1,
+        [2] = 2, -- Second
+        RED = rgb(255, 0, 0),
+        MIX = { 1, 2 } -- Mixed
+    }
+
+    ---@enum (key) Level
+    local Level = {
+        -- plain comment
+        DEBUG = 1,
+        --[[ block
+        comment ]]
+        ---Only the errors
+        ERROR = 2,
+        -- trailing comment
+    }
+
+    ---@enum Empty
+    local Empty = {}
+
+    return U
+    "#;
+
+    assert_eq!(
+        lemmy!(src),
+        "\
+Color                                                                    *Color*
+
+    Variants: ~
+        [\"dark-red\"]  (1)
+        [2]           (2)               Second
+        RED           (rgb(255, 0, 0))
+        MIX           ({ 1, 2 })        Mixed
+
+
+Level                                                                    *Level*
+
+    Variants: ~
+        DEBUG  (1)
+        ERROR  (2)  Only the errors
+
+
+"
+    );
+}
+
+#[test]
+fn operators() {
+    let src = "
+    local U = {}
+
+    ---2D vector
+    ---@class Vec
+    ---@field x number
+    ---@field y number
+    ---@operator add(Vec): Vec
+    ---@operator mul(number|Vec): Vec
+    ---@operator unm: Vec
+
+    return U
+    ";
+
+    assert_eq!(
+        lemmy!(src),
+        "\
+Vec                                                                        *Vec*
+    2D vector
+
+    Fields: ~
+        {x}  (number)
+        {y}  (number)
+
+    Operators: ~
+        add(Vec)         (Vec)
+        mul(number|Vec)  (Vec)
+        unm              (Vec)
+
+
+"
+    );
+}
+
+#[test]
+fn interleaved_operators() {
+    let src = "
+    local U = {}
+
+    ---@class Size
+    ---@field w number
+    ---@operator add(Size): Size
+    ---@field h number
+    ---@see Vec
+
+    return U
+    ";
+
+    assert_eq!(
+        lemmy!(src),
+        "\
+Size                                                                      *Size*
+
+    Fields: ~
+        {w}  (number)
+        {h}  (number)
+
+    Operators: ~
+        add(Size)  (Size)
+
+    See: ~
+        |Vec|
+
+
+"
+    );
+}
+
+#[test]
+fn multiple_parents() {
+    let src = "
+    local U = {}
+
+    ---@class (exact) Mutant : XMen, my.mod.Base
+    ---@field power number Power quantifier
+
+    ---@class Names: Stack<string>
+
+    return U
+    ";
+
+    assert_eq!(
+        lemmy!(src),
+        "\
+Mutant : |XMen|, |my.mod.Base|                                          *Mutant*
+
+    Fields: ~
+        {power}  (number)  Power quantifier
+
+
+Names : |Stack|<string>                                                  *Names*
+
+
+"
+    );
+}
+
+#[test]
+fn index_fields() {
+    let src = r#"
+    local U = {}
+
+    ---Handlers by the event name
+    ---@class Handlers
+    ---@field [string] fun(ev: string) Handler of the event
+    ---@field ["on-exit"] fun() Exit handler
+    ---@field [integer] Item
+    ---@field name? string
+
+    return U
+    "#;
+
+    assert_eq!(
+        lemmy!(src),
+        "\
+Handlers                                                              *Handlers*
+    Handlers by the event name
+
+    Fields: ~
+        [string]     (fun(ev:string))  Handler of the event
+        [\"on-exit\"]  (fun())           Exit handler
+        [integer]    (Item)
+        {name?}      (string)
+
+
+"
+    );
+}
+
+#[test]
+fn admonitions() {
+    let src = "
+    ---@mod my.mod My module
+    ---@warning Experimental module
+
+    local U = {}
+
+    ---Runs the job
+    ---@note This is blocking
+    ---@warning [[
+    ---Don't call it inside the
+    ---fast event
+    ---@warning ]]
+    ---@param cmd string
+    function U.run(cmd) end
+
+    ---Pi
+    ---@note Approximate
+    ---@type number
+    U.PI = 3.14
+
+    return U
+    ";
+
+    assert_eq!(
+        lemmy!(src),
+        "\
+==============================================================================
+My module                                                               *my.mod*
+
+    Warning: ~
+        Experimental module
+
+U.run({cmd})                                                             *U.run*
+    Runs the job
+
+    Note: ~
+        This is blocking
+
+    Warning: ~
+        Don't call it inside the
+        fast event
+
+    Parameters: ~
+        {cmd}  (string)
+
+
+U.PI                                                                      *U.PI*
+    Pi
+
+    Note: ~
+        Approximate
+
+    Type: ~
+        (number)
+
+
+"
+    );
+}
+
+#[test]
+fn tags_in_any_order() {
+    let src = r#"
+    local U = {}
+
+    ---Reads the file
+    ---@param path string
+    ---@version 5.1
+    ---@since 1.0
+    ---@return string
+    ---@nodiscard
+    ---@see U.write
+    ---@deprecated Use |U.load|
+    ---@note Blocks the editor
+    ---@overload fun(fd: integer): string
+    ---@async
+    function U.read(path)
+    end
+
+    return U
+    "#;
+
+    assert_eq!(
+        lemmy!(src),
+        "\
+U.read({path})                                                          *U.read*
+U.read({fd}): string
+    Reads the file
+
+    Deprecated: ~
+        Use |U.load|
+
+    Since: ~
+        1.0
+
+    Lua version: ~
+        5.1
+
+    Note: ~
+        Blocks the editor
+
+    Attributes: ~
+        nodiscard, async
+
+    Parameters: ~
+        {path}  (string)
+
+    Returns: ~
+        (string)
+
+    See: ~
+        |U.write|
+
+
+"
+    );
+}
+
+#[test]
+fn deprecated_anywhere() {
+    let src = r#"
+    local U = {}
+
+    ---@alias Foo string
+    ---@deprecated
+
+    ---@class Bar
+    ---@field a string
+    ---@deprecated Use |Foo|
+
+    ---@deprecated
+    ---@alias Baz number
+
+    ---PI
+    ---@type number
+    ---@see Foo
+    ---@deprecated
+
+    U.pi = 3.14
+
+    return U
+    "#;
+
+    assert_eq!(
+        lemmy!(src),
+        "\
+Foo                                                                        *Foo*
+
+    Deprecated: ~
+
+    Type: ~
+        string
+
+
+Bar                                                                        *Bar*
+
+    Deprecated: ~
+        Use |Foo|
+
+    Fields: ~
+        {a}  (string)
+
+
+Baz                                                                        *Baz*
+
+    Deprecated: ~
+
+    Type: ~
+        number
+
+
+U.pi                                                                      *U.pi*
+    PI
+
+    Deprecated: ~
+
+    Type: ~
+        (number)
+
+    See: ~
+        |Foo|
+
+
+"
+    );
+}
+
+#[test]
+fn versions_anywhere() {
+    let src = r#"
+    local U = {}
+
+    ---@class Foo
+    ---@since 1.0
+    ---@field a string
+    ---@version JIT
+
+    ---@version 5.1
+    ---@since 2.0
+    ---@alias Bar string
+
+    return U
+    "#;
+
+    assert_eq!(
+        lemmy!(src),
+        "\
+Foo                                                                        *Foo*
+
+    Since: ~
+        1.0
+
+    Lua version: ~
+        JIT
+
+    Fields: ~
+        {a}  (string)
+
+
+Bar                                                                        *Bar*
+
+    Since: ~
+        2.0
+
+    Lua version: ~
+        5.1
+
+    Type: ~
+        string
+
+
+"
+    );
+}
+
+#[test]
+fn admonitions_anywhere() {
+    let src = "
+    ---@mod my.mod My module
+    ---@note Loaded lazily
+    ---@deprecated
+
+    local U = {}
+
+    ---@class Job
+    ---@note Reused after exit
+    ---@field id integer
+    ---@warning [[
+    ---Not thread safe
+    ---@warning ]]
+
+    ---Stops the job
+    ---@param id integer
+    ---@note Waits for the exit
+    function U.stop(id) end
+
+    return U
+    ";
+
+    assert_eq!(
+        lemmy!(src),
+        "\
+==============================================================================
+My module                                                               *my.mod*
+
+    Deprecated: ~
+
+    Note: ~
+        Loaded lazily
+
+Job                                                                        *Job*
+
+    Note: ~
+        Reused after exit
+
+    Warning: ~
+        Not thread safe
+
+    Fields: ~
+        {id}  (integer)
+
+
+U.stop({id})                                                            *U.stop*
+    Stops the job
+
+    Note: ~
+        Waits for the exit
+
+    Parameters: ~
+        {id}  (integer)
+
+
+"
+    );
+}
+
+#[test]
+fn empty_lines_in_block() {
+    let src = r#"
+    local U = {}
+
+    ---Some description
+
+    ---@param x string
+    function U.foo(x) end
+
+    ---Desc
+    ---@param y string
+    ---@see U.foo
+
+    ---@usage `U.bar(1)`
+    function U.bar(y) end
+
+    ---@class Point
+    ---@field a number
+
+    ---@field b number
+
+    ---@note Immutable
+    ---@field c number
+
+    ---@alias Side
+    ---| 'a'
+
+    ---| 'b'
+
+    return U
+    "#;
+
+    assert_eq!(
+        lemmy!(src),
+        "\
+U.foo({x})                                                               *U.foo*
+    Some description
+
+    Parameters: ~
+        {x}  (string)
+
+
+U.bar({y})                                                               *U.bar*
+    Desc
+
+    Parameters: ~
+        {y}  (string)
+
+    See: ~
+        |U.foo|
+
+    Usage: ~
+>lua
+        U.bar(1)
+<
+
+
+Point                                                                    *Point*
+
+    Note: ~
+        Immutable
+
+    Fields: ~
+        {a}  (number)
+        {b}  (number)
+        {c}  (number)
+
+
+Side                                                                      *Side*
+
+    Variants: ~
+        (\"a\")
+        (\"b\")
+
+
+"
+    );
+}
+
 #[test]
 fn usage() {
     let src = "
@@ -790,3 +1565,46 @@ Config:get()                                                        *Config:get*
 "
     )
 }
+
+#[test]
+fn help_tags() {
+    let src = "
+    ---@mod mod.intro Introduction
+
+    local U = {}
+
+    ---@tag kinda.module
+
+    ---Not a tag: a*b*c, *inside space*
+    function U.sum() end
+
+    ---@class Human
+    ---@field legs number
+
+    return U
+    ";
+
+    let mut lemmy = LemmyHelp::default();
+    let s = Settings::default();
+    lemmy.for_help(src, &s).unwrap();
+
+    assert_eq!(
+        VimDoc::from_emmy(&lemmy, &s).tags(),
+        Ok(vec!["Human", "U.sum", "kinda.module", "mod.intro"])
+    );
+
+    let dup = "
+    local U = {}
+
+    ---@tag U.sum
+
+    function U.sum() end
+
+    return U
+    ";
+
+    let mut lemmy = LemmyHelp::default();
+    lemmy.for_help(dup, &s).unwrap();
+
+    assert_eq!(VimDoc::from_emmy(&lemmy, &s).tags(), Err("U.sum"));
+}
@@ -0,0 +1,67 @@
+use lemmy_help::ctags::Ctags;
+
+#[test]
+fn ctags() {
+    let human = r#"---@mod my-plugin.human Human module
+
+local U = {}
+
+---The Homosapien
+---@class Human
+---@field legs number Total number of legs
+---@field private hands number
+---@field friend? Human
+
+---@alias Lines string[] All the lines in the buffer
+
+---Creates a Human
+---@param legs number
+---@return Human
+function U.create(legs)
+    return setmetatable({ legs = legs }, { __index = U })
+end
+
+---Human's name
+---@type string
+U.name = 'Sapien'
+
+return U
+"#;
+
+    let other = r#"
+local M = {}
+
+---Prints the human
+---@param human Human
+function M:print(human)
+    print(human)
+end
+
+return M
+"#;
+
+    let mut ctags = Ctags::new();
+    ctags.parse(human, "lua/human.lua").unwrap();
+    ctags.parse(other, "lua/other.lua").unwrap();
+
+    assert_eq!(
+        ctags.to_string(),
+        format!(
+            "\
+!_TAG_FILE_FORMAT\t2\t/extended format; --format=1 will not append ;\" to lines/
+!_TAG_FILE_SORTED\t1\t/0=unsorted, 1=sorted, 2=foldcase/
+!_TAG_PROGRAM_NAME\tlemmy-help\t//
+!_TAG_PROGRAM_VERSION\t{}\t//
+Human\tlua/human.lua\t6;\"\tkind:class\tline:6
+Lines\tlua/human.lua\t11;\"\tkind:alias\tline:11
+M:print\tlua/other.lua\t6;\"\tkind:function\tline:6
+U.create\tlua/human.lua\t16;\"\tkind:function\tline:16
+U.name\tlua/human.lua\t22;\"\tkind:field\tline:22
+friend\tlua/human.lua\t9;\"\tkind:field\tline:9\tclass:Human
+hands\tlua/human.lua\t8;\"\tkind:field\tline:8\tclass:Human
+legs\tlua/human.lua\t7;\"\tkind:field\tline:7\tclass:Human
+",
+            env!("CARGO_PKG_VERSION")
+        )
+    );
+}
@@ -0,0 +1,110 @@
+use lemmy_help::{html::Html, FromEmmy, LemmyHelp, Settings};
+
+macro_rules! lemmy {
+    ($($src: expr),*) => {{
+        let mut lemmy = LemmyHelp::default();
+        let s = Settings::default();
+        $(
+            lemmy.for_help($src, &s).unwrap();
+        )*
+        Html::from_emmy(&lemmy, &s)
+    }};
+}
+
+const SRC: &str = r#"
+---@toc my-plugin.contents
+
+---@mod my-plugin.human Human module
+
+local U = {}
+
+---The Homosapien
+---@class Human
+---@field legs number Total number of legs
+---@field friend? Human
+
+---Creates a Human
+---@param legs number|Human
+---@return Human
+---@see Human
+---@see vim.unknown
+---@usage `require('Human').create(2)`
+function U.create(legs)
+    return setmetatable({ legs = legs }, { __index = U })
+end
+
+return U
+"#;
+
+#[test]
+fn pages() {
+    let html = lemmy!(SRC);
+
+    let pages = html
+        .pages()
+        .iter()
+        .map(|p| (p.name.as_str(), p.title.as_str()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        pages,
+        [("index", "index"), ("my-plugin.human", "Human module")]
+    );
+
+    assert_eq!(
+        html.to_string(),
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>index</title>
+</head>
+<body>
+<h1 id="my-plugin.contents">Table of Contents</h1>
+<ul>
+<li><a href="my-plugin.human.html">Human module</a></li>
+</ul>
+</body>
+</html>
+"#
+    );
+}
+
+#[test]
+fn links() {
+    let html = lemmy!(SRC);
+
+    assert_eq!(
+        html.pages()[1].body,
+        r#"<h1 id="my-plugin.human">Human module</h1>
+<section id="Human">
+<h3><code>Human</code></h3>
+<p>The Homosapien</p>
+<h4>Fields</h4>
+<table>
+<tr><td><code>legs</code></td><td><code>number</code></td><td>Total number of legs</td></tr>
+<tr><td><code>friend?</code></td><td><code><a href="my-plugin.human.html#Human">Human</a></code></td><td></td></tr>
+</table>
+</section>
+<section id="U.create">
+<h3><code>U.create(legs)</code></h3>
+<p>Creates a Human</p>
+<h4>Parameters</h4>
+<table>
+<tr><td><code>legs</code></td><td><code>number|<a href="my-plugin.human.html#Human">Human</a></code></td><td></td></tr>
+</table>
+<h4>Returns</h4>
+<table>
+<tr><td><code><a href="my-plugin.human.html#Human">Human</a></code></td><td></td></tr>
+</table>
+<h4>See</h4>
+<ul>
+<li><a href="my-plugin.human.html#Human"><code>Human</code></a></li>
+<li><code>vim.unknown</code></li>
+</ul>
+<h4>Usage</h4>
+<pre><code class="language-lua">require('Human').create(2)</code></pre>
+</section>
+"#
+    );
+}
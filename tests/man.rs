@@ -0,0 +1,128 @@
+use lemmy_help::{man::Man, FromEmmy, LemmyHelp, Settings};
+
+macro_rules! lemmy {
+    ($($src: expr),*) => {{
+        let mut lemmy = LemmyHelp::default();
+        let s = Settings::default();
+        $(
+            lemmy.for_help($src, &s).unwrap();
+        )*
+        Man::from_emmy(&lemmy, &s).to_string()
+    }};
+}
+
+#[test]
+fn module_and_functions() {
+    let src = r#"
+    ---@mod my-tool.api Public API
+
+    local U = {}
+
+    ---Human being
+    ---@class Human
+    ---@field legs number Total number of legs
+
+    ---Subtract second from the first integer
+    ---@param this number
+    ---@param that? number Second number
+    ---@return number _ Difference
+    ---@see my-tool.add
+    ---@usage [[
+    ---local M = require("my-tool")
+    ---print(M.sub(10, 5))
+    ---@usage ]]
+    function U.sub(this, that)
+        return this - that
+    end
+
+    return U
+    "#;
+
+    assert_eq!(
+        lemmy!(src),
+        r#".TH "my\-tool.api" 1
+.SH "Public API"
+.SS "Human"
+.PP
+Human being
+.br
+.PP
+\fBFields:\fR
+.RS
+.TP
+\fBlegs\fR (\fInumber\fR)
+Total number of legs
+.br
+.RE
+.SS "U.sub(this, that?)"
+.PP
+Subtract second from the first integer
+.br
+.PP
+\fBParameters:\fR
+.RS
+.TP
+\fBthis\fR (\fInumber\fR)
+.TP
+\fBthat?\fR (\fInumber\fR)
+Second number
+.br
+.RE
+.PP
+\fBReturns:\fR
+.RS
+.TP
+\fInumber\fR
+Difference
+.br
+.RE
+.PP
+\fBSee:\fR
+.RS
+\fBmy\-tool.add\fR
+.br
+.RE
+.PP
+\fBUsage:\fR
+.RS
+.EX
+local M = require("my\-tool")
+print(M.sub(10, 5))
+.EE
+.RE
+"#
+    );
+}
+
+#[test]
+fn alias_and_escape() {
+    let src = r#"
+    local U = {}
+
+    ---.dot at the start and a \ backslash
+    ---@alias Mode
+    ---| '"n"' # Normal mode
+    ---| '"v"'
+
+    return U
+    "#;
+
+    assert_eq!(
+        lemmy!(src),
+        r#".TH "" 1
+.SS "Mode"
+.PP
+\&.dot at the start and a \e backslash
+.br
+.PP
+\fBVariants:\fR
+.RS
+.TP
+\fI"n"\fR
+Normal mode
+.TP
+\fI"v"\fR
+.RE
+"#
+    );
+}
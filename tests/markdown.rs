@@ -0,0 +1,178 @@
+use lemmy_help::{markdown::Markdown, FromEmmy, LemmyHelp, Settings};
+
+macro_rules! lemmy {
+    ($($src: expr),*) => {{
+        let mut lemmy = LemmyHelp::default();
+        let s = Settings::default();
+        $(
+            lemmy.for_help($src, &s).unwrap();
+        )*
+        Markdown::from_emmy(&lemmy, &s).to_string()
+    }};
+}
+
+#[test]
+fn module_and_toc() {
+    let src = "
+    ---@toc my-plugin.contents
+
+    ---@mod first.module First Module
+    ---@brief [[
+    ---Some text about the first module
+    ---@brief ]]
+
+    ---@mod second.module
+
+    local U = {}
+
+    return U
+    ";
+
+    assert_eq!(
+        lemmy!(src),
+        "\
+<a name=\"my-plugin.contents\"></a>
+## Table of Contents
+
+- [First Module](#first.module)
+- [second.module](#second.module)
+
+<a name=\"first.module\"></a>
+## First Module
+
+Some text about the first module
+
+<a name=\"second.module\"></a>
+## second.module
+
+"
+    );
+}
+
+#[test]
+fn functions() {
+    let src = r#"
+    local U = {}
+
+    ---Subtract second from the first integer
+    ---@param this number
+    ---@param that? number|string Second number
+    ---@return number
+    ---Some secret number that
+    ---we don't know about
+    ---@see U.sum
+    ---@usage [[
+    ---local M = require("module.U")
+    ---
+    ---print(M.sub(10 - 5))
+    ---@usage ]]
+    function U.sub(this, that)
+        return this - that
+    end
+
+    return U
+    "#;
+
+    assert_eq!(
+        lemmy!(src),
+        r#"<a name="U.sub"></a>
+### `U.sub(this, that?)`
+
+Subtract second from the first integer
+
+**Parameters**
+
+| Name | Type | Description |
+| --- | --- | --- |
+| `this` | `number` | |
+| `that?` | `number\|string` | Second number |
+
+**Returns**
+
+| Type | Description |
+| --- | --- |
+| `number` | Some secret number that<br>we don't know about |
+
+**See**
+
+- [`U.sum`](#U.sum)
+
+**Usage**
+
+```lua
+local M = require("module.U")
+
+print(M.sub(10 - 5))
+```
+
+"#
+    );
+}
+
+#[test]
+fn class_alias_and_type() {
+    let src = r#"
+    local U = {}
+
+    ---The Homosapien
+    ---@class Human : Mammal
+    ---@field legs number Total number of legs
+    ---@field private secret string
+
+    ---@alias VMode
+    ---| '"line"' # Vertical motion
+    ---| 'v'
+
+    ---@alias Lines string[]
+
+    ---Global vim mode
+    ---@type VMode current mode
+    U.VMODE = 'line'
+
+    return U
+    "#;
+
+    assert_eq!(
+        lemmy!(src),
+        r#"<a name="Human"></a>
+### `Human : Mammal`
+
+The Homosapien
+
+**Fields**
+
+| Name | Type | Description |
+| --- | --- | --- |
+| `legs` | `number` | Total number of legs |
+
+<a name="VMode"></a>
+### `VMode`
+
+**Variants**
+
+| Variant | Description |
+| --- | --- |
+| `"line"` | Vertical motion |
+| `"v"` | |
+
+<a name="Lines"></a>
+### `Lines`
+
+**Type**
+
+`string[]`
+
+<a name="U.VMODE"></a>
+### `U.VMODE`
+
+Global vim mode
+
+**Type**
+
+| Type | Description |
+| --- | --- |
+| `VMode` | current mode |
+
+"#
+    );
+}
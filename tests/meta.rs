@@ -0,0 +1,147 @@
+use lemmy_help::{meta::Meta, FromEmmy, LemmyHelp, Nodes, Settings};
+
+macro_rules! lemmy {
+    ($($src: expr),*) => {{
+        let mut lemmy = LemmyHelp::default();
+        let s = Settings::default();
+        $(
+            lemmy.for_help($src, &s).unwrap();
+        )*
+        Meta::from_emmy(&lemmy, &s).to_string()
+    }};
+}
+
+const CODE: &str = r#"
+---@mod mod.Human Human module
+
+local U = {}
+
+---The Homosapien
+---@class Human : Mammal
+---@field legs number Total number of legs
+---Whether the human has
+---a brain
+---@field brain? boolean
+---@field private secret string
+
+---@alias Lines string[]
+
+---Vim operator-mode motions.
+---@alias VMode
+---| '"line"' # Vertical motion
+---| `some.ident`
+
+---NOTE: Local functions are not part of the API
+local function mul(this, that)
+    return this * that
+end
+
+---Subtract second from the first integer
+---@param this number
+---@param that? number Second number
+---and it is optional
+---@return number _ The result
+---@return boolean #Whether it is positive
+---@see U.add
+function U.sub(this, that)
+    return this - that
+end
+
+---@private
+function U.hidden() end
+
+---Global vim mode
+---@type VMode current mode
+U.VMODE = 'line'
+
+---Creates a Human
+---@param ... string
+---@return Human
+function U:create(...)
+    return setmetatable({}, { __index = self })
+end
+
+return U
+"#;
+
+#[test]
+fn meta() {
+    assert_eq!(
+        lemmy!(CODE),
+        r#"---@meta
+
+---The Homosapien
+---@class Human : Mammal
+---@field legs number Total number of legs
+---a brain
+---@field brain? boolean Whether the human has
+
+---@alias Lines string[]
+
+---Vim operator-mode motions.
+---@alias VMode
+---| '"line"' # Vertical motion
+---| `some.ident`
+
+local U = {}
+
+---Subtract second from the first integer
+---@param this number
+---@param that? number Second number
+---and it is optional
+---@return number _ The result
+---@return boolean #Whether it is positive
+---@see U.add
+function U.sub(this, that) end
+
+---Global vim mode
+---@type VMode current mode
+U.VMODE = nil
+
+---Creates a Human
+---@param ... string
+---@return Human
+function U:create(...) end
+
+return U
+"#
+    );
+}
+
+#[test]
+fn round_trip() {
+    let meta = lemmy!(CODE);
+
+    let mut lemmy = LemmyHelp::default();
+    lemmy.parse(&meta).unwrap();
+
+    // Module and the private function are stripped away, `return U` is kept as the export
+    assert_eq!(lemmy.nodes().len(), 7);
+    assert_eq!(lemmy!(&meta), meta);
+}
+
+#[test]
+fn enum_keys() {
+    let src = r#"
+    local U = {}
+
+    ---@enum (key) Level
+    local Level = {
+        DEBUG = 1,
+    }
+
+    return U
+    "#;
+
+    assert_eq!(
+        lemmy!(src),
+        "\
+---@meta
+
+---@enum (key) Level
+local Level = {
+    DEBUG = 1,
+}
+"
+    );
+}
@@ -0,0 +1,45 @@
+use lemmy_help::{parser::Node, LemmyHelp, Nodes};
+
+const CODE: &str = r#"
+---@mod mod.Human Human module
+
+local U = {}
+
+---The Homosapien
+---@class Human
+---@field legs number Total number of legs
+---@field private secret? string|string[]
+---@deprecated
+---@field arms number
+
+---@alias VMode
+---| '"line"' # Vertical motion
+---| `some.ident`
+
+---Creates a Human
+---@deprecated
+---@param legs integer
+---@return Human
+---@usage `require('Human').create(2)`
+function U.create(legs)
+    return setmetatable({ legs = legs }, { __index = U })
+end
+
+return U
+"#;
+
+#[test]
+fn round_trip() {
+    let mut lemmy = LemmyHelp::new();
+    lemmy.parse(CODE).unwrap();
+
+    let json = serde_json::to_string(lemmy.nodes()).unwrap();
+    let nodes: Vec<Node> = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(nodes.len(), lemmy.nodes().len());
+    assert_eq!(serde_json::to_string(&nodes).unwrap(), json);
+
+    // Deprecated without a message must not be confused with not deprecated
+    assert_eq!(json.matches(r#""deprecated":{"message":null}"#).count(), 2);
+    assert_eq!(nodes.iter().filter(|n| n.is_deprecated()).count(), 1);
+}
@@ -41,16 +41,11 @@ fn types() {
 
     check!(
         "(string|number|table<string, string[]>)[]",
-        Ty::Array(b!(Ty::Union(
-            b!(Ty::String),
-            b!(Ty::Union(
-                b!(Ty::Number),
-                b!(Ty::Table(Some((
-                    b!(Ty::String),
-                    b!(Ty::Array(b!(Ty::String)))
-                ))))
-            ))
-        )))
+        Ty::Array(b!(Ty::Union(vec![
+            Ty::String,
+            Ty::Number,
+            Ty::Table(Some((b!(Ty::String), b!(Ty::Array(b!(Ty::String)))))),
+        ])))
     );
 
     check!(
@@ -70,7 +65,7 @@ fn types() {
             b!(Ty::String),
             b!(Ty::Fun(
                 vec![(Name::Req("a".into()), Ty::String)],
-                Some(vec![Ty::String])
+                Some(vec![(None, Ty::String)])
             ))
         )))
     );
@@ -87,10 +82,11 @@ fn types() {
         "table<string, string|string[]|boolean>[]",
         Ty::Array(b!(Ty::Table(Some((
             b!(Ty::String),
-            b!(Ty::Union(
-                b!(Ty::String),
-                b!(Ty::Union(b!(Ty::Array(b!(Ty::String))), b!(Ty::Boolean)))
-            ))
+            b!(Ty::Union(vec![
+                Ty::String,
+                Ty::Array(b!(Ty::String)),
+                Ty::Boolean
+            ]))
         )))))
     );
 
@@ -103,10 +99,7 @@ fn types() {
                 (Name::Req("a".into()), Ty::String),
                 (
                     Name::Req("b".into()),
-                    Ty::Union(
-                        b!(Ty::String),
-                        b!(Ty::Union(b!(Ty::Number), b!(Ty::Boolean)))
-                    )
+                    Ty::Union(vec![Ty::String, Ty::Number, Ty::Boolean])
                 ),
                 (
                     Name::Req("c".into()),
@@ -115,8 +108,8 @@ fn types() {
                 (Name::Opt("d".into()), Ty::Ref("SomeClass".into())),
             ],
             Some(vec![
-                Ty::Number,
-                Ty::Union(b!(Ty::String), b!(Ty::Array(b!(Ty::String))))
+                (None, Ty::Number),
+                (None, Ty::Union(vec![Ty::String, Ty::Array(b!(Ty::String))]))
             ])
         )
     );
@@ -144,33 +137,27 @@ fn types() {
                 ),
                 (Name::Req(
                     "e".into()),
-                    Ty::Union(
-                        b!(Ty::String),
-                        b!(Ty::Union(
-                            b!(Ty::Array(b!(Ty::String))),
-                            b!(Ty::Union(
-                                b!(Ty::Table(Some((b!(Ty::String), b!(Ty::String))))),
-                                b!(Ty::Fun(
-                                    vec![(Name::Req(
-                                        "y".into()),
-                                        Ty::Union(
-                                            b!(Ty::Array(b!(Ty::String))),
-                                            b!(Ty::Union(
-                                                b!(Ty::Dict(vec![
-                                                    (Name::Req("get".into()), Ty::Function)
-                                                ])),
-                                                b!(Ty::String)
-                                            ))
-                                        )
-                                    ),],
-                                    Some(vec![Ty::String])
-                                ))
-                            ))
-                        ))
-                    )
+                    Ty::Union(vec![
+                        Ty::String,
+                        Ty::Array(b!(Ty::String)),
+                        Ty::Table(Some((b!(Ty::String), b!(Ty::String)))),
+                        Ty::Fun(
+                            vec![(Name::Req(
+                                "y".into()),
+                                Ty::Union(vec![
+                                    Ty::Array(b!(Ty::String)),
+                                    Ty::Dict(vec![
+                                        (Name::Req("get".into()), Ty::Function)
+                                    ]),
+                                    Ty::String
+                                ])
+                            ),],
+                            Some(vec![(None, Ty::String)])
+                        )
+                    ])
                 )
             ],
-            Some(vec![Ty::Table(Some((b!(Ty::String), b!(Ty::String))))])
+            Some(vec![(None, Ty::Table(Some((b!(Ty::String), b!(Ty::String)))))])
         )
     );
 
@@ -200,38 +187,231 @@ fn types() {
 
     check!(
         r#"'"g@"'|string[]|'"g@$"'|number"#,
-        Ty::Union(
-            b!(Ty::Member(Member::Literal(r#""g@""#.into()))),
-            b!(Ty::Union(
-                b!(Ty::Array(b!(Ty::String))),
-                b!(Ty::Union(
-                    b!(Ty::Member(Member::Literal(r#""g@$""#.into()))),
-                    b!(Ty::Number)
-                ))
-            ))
-        )
+        Ty::Union(vec![
+            Ty::Member(Member::Literal(r#""g@""#.into())),
+            Ty::Array(b!(Ty::String)),
+            Ty::Member(Member::Literal(r#""g@$""#.into())),
+            Ty::Number
+        ])
     );
 
     check!(
         "any|any|string|(string|number)[]|fun(a: string)|table<string, number>|userdata[]",
-        Ty::Union(
-            b!(Ty::Any),
-            b!(Ty::Union(
-                b!(Ty::Any),
-                b!(Ty::Union(
-                    b!(Ty::String),
-                    b!(Ty::Union(
-                        b!(Ty::Array(b!(Ty::Union(b!(Ty::String), b!(Ty::Number))))),
-                        b!(Ty::Union(
-                            b!(Ty::Fun(vec![(Name::Req("a".into()), Ty::String)], None)),
-                            b!(Ty::Union(
-                                b!(Ty::Table(Some((b!(Ty::String), b!(Ty::Number))))),
-                                b!(Ty::Array(b!(Ty::Userdata)))
-                            ))
-                        ))
-                    ))
-                ))
-            ))
+        Ty::Union(vec![
+            Ty::Any,
+            Ty::String,
+            Ty::Array(b!(Ty::Union(vec![Ty::String, Ty::Number]))),
+            Ty::Fun(vec![(Name::Req("a".into()), Ty::String)], None),
+            Ty::Table(Some((b!(Ty::String), b!(Ty::Number)))),
+            Ty::Array(b!(Ty::Userdata))
+        ])
+    );
+}
+
+#[test]
+fn unions() {
+    let type_parse = Lexer::init();
+
+    macro_rules! check {
+        ($s:expr, $ty:expr, $display:expr) => {
+            let ty = match type_parse
+                .parse(concat!("---@type ", $s))
+                .unwrap()
+                .into_iter()
+                .next()
+                .unwrap()
+                .0
+            {
+                lemmy_help::lexer::TagType::Type(ty, _) => ty,
+                _ => unreachable!(),
+            };
+            assert_eq!(ty, $ty);
+            assert_eq!(ty.to_string(), $display);
+        };
+    }
+
+    check!(
+        "string|(number|string)|nil",
+        Ty::Union(vec![Ty::String, Ty::Number, Ty::Nil]),
+        "string|number|nil"
+    );
+
+    check!("(string|string)", Ty::String, "string");
+
+    check!(
+        "(string|number)[]|nil",
+        Ty::Union(vec![
+            Ty::Array(b!(Ty::Union(vec![Ty::String, Ty::Number]))),
+            Ty::Nil
+        ]),
+        "(string|number)[]|nil"
+    );
+
+    check!(
+        "(fun(): string)[]",
+        Ty::Array(b!(Ty::Fun(vec![], Some(vec![(None, Ty::String)])))),
+        "(fun():string)[]"
+    );
+
+    check!(
+        "string?|fun(a: string?): Foo?",
+        Ty::Union(vec![
+            Ty::Opt(b!(Ty::String)),
+            Ty::Fun(
+                vec![(Name::Req("a".into()), Ty::Opt(b!(Ty::String)))],
+                Some(vec![(None, Ty::Opt(b!(Ty::Ref("Foo".into()))))])
+            )
+        ]),
+        "string?|(fun(a:string?):Foo?)"
+    );
+
+    check!(
+        "(fun(): string)|nil",
+        Ty::Union(vec![
+            Ty::Fun(vec![], Some(vec![(None, Ty::String)])),
+            Ty::Nil
+        ]),
+        "(fun():string)|nil"
+    );
+
+    check!(
+        "(string|number)?[]",
+        Ty::Array(b!(Ty::Opt(b!(Ty::Union(vec![Ty::String, Ty::Number]))))),
+        "(string|number)?[]"
+    );
+
+    check!(
+        "Stack<string, Foo<number>[]>|`T`",
+        Ty::Union(vec![
+            Ty::Apply(
+                "Stack".into(),
+                vec![
+                    Ty::String,
+                    Ty::Array(b!(Ty::Apply("Foo".into(), vec![Ty::Number])))
+                ]
+            ),
+            Ty::Member(Member::Ident("T".into()))
+        ]),
+        "Stack<string,Foo<number>[]>|T"
+    );
+
+    check!(
+        "[integer, integer]|[string, fun(): boolean][]",
+        Ty::Union(vec![
+            Ty::Tuple(vec![Ty::Integer, Ty::Integer]),
+            Ty::Array(b!(Ty::Tuple(vec![
+                Ty::String,
+                Ty::Fun(vec![], Some(vec![(None, Ty::Boolean)]))
+            ])))
+        ]),
+        "[integer,integer]|[string,fun():boolean][]"
+    );
+
+    check!(
+        "1|2|-3|0.5|1",
+        Ty::Union(vec![
+            Ty::Num("1".into()),
+            Ty::Num("2".into()),
+            Ty::Num("-3".into()),
+            Ty::Num("0.5".into())
+        ]),
+        "1|2|-3|0.5"
+    );
+
+    check!(
+        "true|false|boolean|trueish",
+        Ty::Union(vec![
+            Ty::Bool(true),
+            Ty::Bool(false),
+            Ty::Boolean,
+            Ty::Ref("trueish".into())
+        ]),
+        "true|false|boolean|trueish"
+    );
+
+    check!(
+        r#"{ [string]: integer, ["key-with-dash"]: boolean, ['lit']: nil, [1]: string, id?: Foo }"#,
+        Ty::Dict(vec![
+            (Name::Index(Ty::String), Ty::Integer),
+            (
+                Name::Index(Ty::Member(Member::Literal("key-with-dash".into()))),
+                Ty::Boolean
+            ),
+            (
+                Name::Index(Ty::Member(Member::Literal("lit".into()))),
+                Ty::Nil
+            ),
+            (Name::Index(Ty::Num("1".into())), Ty::String),
+            (Name::Opt("id".into()), Ty::Ref("Foo".into()))
+        ]),
+        r#"{[string]:integer,["key-with-dash"]:boolean,["lit"]:nil,[1]:string,id?:Foo}"#
+    );
+
+    check!(
+        "fun(...: any): boolean, string?",
+        Ty::Fun(
+            vec![(Name::Req("...".into()), Ty::Any)],
+            Some(vec![(None, Ty::Boolean), (None, Ty::Opt(b!(Ty::String)))])
+        ),
+        "fun(...:any):boolean,string?"
+    );
+
+    check!(
+        "fun(a: string, ...): (ok: boolean, err: string)|nil",
+        Ty::Union(vec![
+            Ty::Fun(
+                vec![
+                    (Name::Req("a".into()), Ty::String),
+                    (Name::Req("...".into()), Ty::Any)
+                ],
+                Some(vec![
+                    (Some("ok".into()), Ty::Boolean),
+                    (Some("err".into()), Ty::String)
+                ])
+            ),
+            Ty::Nil
+        ]),
+        "(fun(a:string,...:any):(ok:boolean,err:string))|nil"
+    );
+
+    check!(
+        "fun(): (string|number)",
+        Ty::Fun(
+            vec![],
+            Some(vec![(None, Ty::Union(vec![Ty::String, Ty::Number]))])
+        ),
+        "fun():string|number"
+    );
+
+    check!(
+        "fun(): (boolean, string)",
+        Ty::Fun(vec![], Some(vec![(None, Ty::Boolean), (None, Ty::String)])),
+        "fun():boolean,string"
+    );
+
+    // Colon without any return type
+    for src in ["---@type fun(): ()", "---@type fun():"] {
+        let tokens = type_parse.parse(src).unwrap();
+        assert!(
+            !matches!(
+                tokens.first(),
+                Some((lemmy_help::lexer::TagType::Type(..), _))
+            ),
+            "{src} is accepted as {tokens:?}"
+        );
+    }
+}
+
+#[test]
+fn type_args_without_space() {
+    let tokens = Lexer::init().parse("---@param x Foo <desc>\n").unwrap();
+
+    assert_eq!(
+        tokens[0].0,
+        lemmy_help::lexer::TagType::Param(
+            Name::Req("x".into()),
+            Ty::Ref("Foo".into()),
+            Some("<desc>".into())
         )
     );
 }
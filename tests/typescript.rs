@@ -0,0 +1,197 @@
+use lemmy_help::{typescript::TypeScript, FromEmmy, LemmyHelp, Settings};
+
+macro_rules! lemmy {
+    ($($src: expr),*) => {{
+        let mut lemmy = LemmyHelp::default();
+        let s = Settings::default();
+        $(
+            lemmy.for_help($src, &s).unwrap();
+        )*
+        TypeScript::from_emmy(&lemmy, &s).to_string()
+    }};
+}
+
+#[test]
+fn module() {
+    let src = r#"
+    ---@mod my.mod My module
+
+    local U = { foo = {} }
+
+    ---A human being
+    ---@class Human : Mammal
+    ---@field name? string|number Name of the human
+    ---@field private secret string
+    ---@field cb fun(a: string, b?: integer): boolean, string
+    ---@field meta { id: integer, tags?: table<string, number[]> }
+    ---@field list (string|number)[]
+
+    ---@alias Mode
+    ---| '"n"' # Normal
+    ---| '"v"'
+
+    ---@alias Callback fun(err?: string)
+
+    ---Sum of the numbers
+    ---@param a number First number
+    ---@param ... integer
+    ---@return number
+    function U.sum(a, ...) end
+
+    ---@return Human
+    function U:new() end
+
+    ---PI but nested
+    ---@type number
+    U.foo.PI = 3.14
+
+    return U
+    "#;
+
+    assert_eq!(
+        lemmy!(src),
+        r#"/** A human being */
+interface Human extends Mammal {
+    /** Name of the human */
+    name?: string | number;
+    cb: (this: void, a: string, b?: number) => LuaMultiReturn<[boolean, string]>;
+    meta: { id: number; tags?: LuaTable<string, number[]> };
+    list: (string | number)[];
+}
+
+type Mode = "n" | "v";
+
+type Callback = (this: void, err?: string) => void;
+
+declare module "my.mod" {
+    /**
+     * Sum of the numbers
+     * @param a First number
+     */
+    export function sum(this: void, a: number, ...args: number[]): number;
+
+    export function new(): Human;
+
+    export namespace foo {
+        /** PI but nested */
+        export const PI: number;
+    }
+}
+"#
+    );
+}
+
+#[test]
+fn namespace() {
+    let src = r#"
+    local U = {}
+
+    ---@param cb fun()
+    function U.on(cb) end
+
+    return U
+    "#;
+
+    assert_eq!(
+        lemmy!(src),
+        "\
+declare namespace U {
+    export function on(this: void, cb: (this: void) => void): void;
+}
+"
+    );
+}
+
+#[test]
+fn dict_keys() {
+    let src = r#"
+    local U = {}
+
+    ---@alias Lookup { [string]: integer, ["key-with-dash"]: boolean, [1]: string, name?: string }
+
+    return U
+    "#;
+
+    assert_eq!(
+        lemmy!(src),
+        "\
+type Lookup = { [key: string]: number; \"key-with-dash\": boolean; 1: string; name?: string };
+"
+    );
+}
+
+#[test]
+fn dotted_names() {
+    let src = r#"
+    local U = {}
+
+    ---@class my.mod.Base
+    ---@field cb fun()|string
+    ---@field on fun(ev: string)?
+
+    ---@alias my.Mode '"n"'|'"v"'
+
+    return U
+    "#;
+
+    assert_eq!(
+        lemmy!(src),
+        r#"declare namespace my.mod {
+    interface Base {
+        cb: ((this: void) => void) | string;
+        on?: (this: void, ev: string) => void;
+    }
+}
+
+declare namespace my {
+    type Mode = "n" | "v";
+}
+"#
+    );
+}
+
+#[test]
+fn enum_keys() {
+    let src = r#"
+    local U = {}
+
+    ---@enum (key) Level
+    local Level = {
+        DEBUG = 1,
+        ["no-op"] = 2,
+        [3] = 3,
+    }
+
+    return U
+    "#;
+
+    assert_eq!(
+        lemmy!(src),
+        "\
+type Level = \"DEBUG\" | \"no-op\" | 3;
+"
+    );
+}
+
+#[test]
+fn optional_params() {
+    let src = r#"
+    local U = {}
+
+    ---@param name string?
+    ---@param opts? table?
+    function U.setup(name, opts)
+    end
+
+    return U
+    "#;
+
+    assert_eq!(
+        lemmy!(src),
+        "\
+declare namespace U {
+    export function setup(this: void, name?: string, opts?: LuaTable): void;
+}
+"
+    );
+}
@@ -184,3 +184,441 @@ M.echo({message}, {opts?})                                              *M.echo*
 "
     );
 }
+
+#[test]
+fn text_width() {
+    let src = "
+---@mod mod.intro Introduction
+---@brief [[
+---Intro
+---@brief ]]
+
+local M = {}
+
+---Prints given value
+---@param message string
+function M.echo(message)
+    return print(message)
+end
+
+return M
+";
+
+    let mut lemmy = LemmyHelp::new();
+    let s = Settings {
+        text_width: 60,
+        ..Default::default()
+    };
+
+    lemmy.for_help(src, &s).unwrap();
+
+    assert_eq!(
+        VimDoc::from_emmy(&lemmy, &s).to_string(),
+        "\
+==========================================================
+Introduction                                     *mod.intro*
+
+Intro
+
+M.echo({message})                                   *M.echo*
+    Prints given value
+
+    Parameters: ~
+        {message}  (string)
+
+
+"
+    );
+}
+
+#[test]
+fn wrap() {
+    let src = "
+local M = {}
+
+---Prints the given message to the world, and this line is long enough to be wrapped
+---because it exceeds the text width.
+---
+---Usage is simple: >lua
+---    M.echo('hello world, this is a long message that should not be wrapped at all')
+---<
+---@param message string The message which is printed to the world, it is long enough to be wrapped
+---@param opts? table
+---@return boolean ok Whether the message was printed successfully to the world or not, long one
+function M.echo(message, opts)
+    return print(message)
+end
+
+return M
+";
+
+    let mut lemmy = LemmyHelp::new();
+    let s = Settings {
+        wrap: true,
+        ..Default::default()
+    };
+
+    lemmy.for_help(src, &s).unwrap();
+
+    assert_eq!(
+        VimDoc::from_emmy(&lemmy, &s).to_string(),
+        "\
+M.echo({message}, {opts?})                                              *M.echo*
+    Prints the given message to the world, and this line is long enough to be
+    wrapped because it exceeds the text width.
+
+    Usage is simple: >lua
+        M.echo('hello world, this is a long message that should not be wrapped at all')
+    <
+
+    Parameters: ~
+        {message}  (string)  The message which is printed to the world, it is
+                             long enough to be wrapped
+        {opts?}    (table)
+
+    Returns: ~
+        (boolean)  Whether the message was printed successfully to the world or
+                   not, long one
+
+
+"
+    );
+}
+
+#[test]
+fn wrap_lists() {
+    let src = "
+local M = {}
+
+---Options:
+---  - foo: does foo
+---  - bar: does bar and this item is long enough to be wrapped onto the next line
+---* first
+---1. second
+---@class Opts
+---@field a_really_long_field_name_which_takes_the_space string Described in many words
+
+return M
+";
+
+    let mut lemmy = LemmyHelp::new();
+    let s = Settings {
+        wrap: true,
+        text_width: 60,
+        ..Default::default()
+    };
+
+    lemmy.for_help(src, &s).unwrap();
+
+    assert_eq!(
+        VimDoc::from_emmy(&lemmy, &s).to_string(),
+        "\
+Opts                                                  *Opts*
+    Options:
+      - foo: does foo
+      - bar: does bar and this item is long enough to be
+        wrapped onto the next line
+    * first
+    1. second
+
+    Fields: ~
+        {a_really_long_field_name_which_takes_the_space}  (string)  Described in many words
+
+
+"
+    );
+}
+
+#[test]
+fn expand_opt_type() {
+    let src = "
+local M = {}
+
+---Prints given value
+---@param message string?
+---@param opts? table?
+---@param hooks (fun(msg: string?))[]
+---@return boolean?
+function M.echo(message, opts, hooks)
+    return print(message)
+end
+
+return M
+";
+
+    let mut lemmy = LemmyHelp::new();
+    let s = Settings {
+        expand_opt: true,
+        ..Default::default()
+    };
+
+    lemmy.for_help(src, &s).unwrap();
+
+    assert_eq!(
+        VimDoc::from_emmy(&lemmy, &s).to_string(),
+        "\
+M.echo({message}, {opts?}, {hooks})                                     *M.echo*
+    Prints given value
+
+    Parameters: ~
+        {message}  (nil|string)
+        {opts}     (nil|table)
+        {hooks}    (fun(msg:nil|string)[])
+
+    Returns: ~
+        (nil|boolean)
+
+
+"
+    );
+}
+
+#[test]
+fn expand_opt_alias() {
+    let src = "
+local M = {}
+
+---@alias MaybeStr string?
+
+---@class Vec
+---@operator add(Vec?): Vec?
+
+return M
+";
+
+    let mut lemmy = LemmyHelp::new();
+    let s = Settings {
+        expand_opt: true,
+        ..Default::default()
+    };
+
+    lemmy.for_help(src, &s).unwrap();
+
+    assert_eq!(
+        VimDoc::from_emmy(&lemmy, &s).to_string(),
+        "\
+MaybeStr                                                              *MaybeStr*
+
+    Type: ~
+        nil|string
+
+
+Vec                                                                        *Vec*
+
+    Operators: ~
+        add(nil|Vec)  (nil|Vec)
+
+
+"
+    );
+}
+
+#[test]
+fn deprecated() {
+    let src = "
+local M = {}
+
+---Echos the message
+---@deprecated Use |M.print| instead
+---@param message string
+function M.echo(message)
+    return print(message)
+end
+
+---@class Config
+---@field name string
+---@deprecated
+---@field old boolean Old field
+
+---@deprecated
+---@alias Mode 'n'|'v'
+
+---Prints the message
+---@param message string
+function M.print(message)
+    return print(message)
+end
+
+return M
+";
+
+    let mut lemmy = LemmyHelp::new();
+    let s = Settings::default();
+    lemmy.for_help(src, &s).unwrap();
+
+    assert_eq!(
+        VimDoc::from_emmy(&lemmy, &s).to_string(),
+        "\
+M.echo({message})                                                       *M.echo*
+    Echos the message
+
+    Deprecated: ~
+        Use |M.print| instead
+
+    Parameters: ~
+        {message}  (string)
+
+
+Config                                                                  *Config*
+
+    Fields: ~
+        {name}  (string)
+        {old}   (boolean)  Deprecated
+                           Old field
+
+
+Mode                                                                      *Mode*
+
+    Deprecated: ~
+
+    Type: ~
+        \"n\"|\"v\"
+
+
+M.print({message})                                                     *M.print*
+    Prints the message
+
+    Parameters: ~
+        {message}  (string)
+
+
+"
+    );
+
+    let mut lemmy = LemmyHelp::new();
+    let s = Settings {
+        omit_deprecated: true,
+        ..Default::default()
+    };
+    lemmy.for_help(src, &s).unwrap();
+
+    assert_eq!(
+        VimDoc::from_emmy(&lemmy, &s).to_string(),
+        "\
+Config                                                                  *Config*
+
+    Fields: ~
+        {name}  (string)
+
+
+M.print({message})                                                     *M.print*
+    Prints the message
+
+    Parameters: ~
+        {message}  (string)
+
+
+"
+    );
+
+    let mut lemmy = LemmyHelp::new();
+    lemmy
+        .for_help(
+            &format!("---@mod mod.old Old module\n---@deprecated\n{src}"),
+            &s,
+        )
+        .unwrap();
+
+    assert_eq!(VimDoc::from_emmy(&lemmy, &s).to_string(), "");
+}
+
+#[test]
+fn changelog() {
+    let src = "
+---@mod my.mod My module
+
+local M = {}
+
+---Echos the message
+---@since 0.2.0
+---@version 5.1, JIT
+---@param message string
+function M.echo(message)
+    return print(message)
+end
+
+---@since 0.1.0
+---@class Config
+---@field name string
+
+---@since 0.10.0
+---@alias Mode 'n'|'v'
+
+---Pi
+---@since 0.2.0
+---@type number
+M.PI = 3.14
+
+return M
+";
+
+    let mut lemmy = LemmyHelp::new();
+    let s = Settings {
+        changelog: true,
+        ..Default::default()
+    };
+    lemmy.for_help(src, &s).unwrap();
+
+    assert_eq!(
+        VimDoc::from_emmy(&lemmy, &s).to_string(),
+        "\
+==============================================================================
+My module                                                               *my.mod*
+
+M.echo({message})                                                       *M.echo*
+    Echos the message
+
+    Since: ~
+        0.2.0
+
+    Lua version: ~
+        5.1, JIT
+
+    Parameters: ~
+        {message}  (string)
+
+
+Config                                                                  *Config*
+
+    Since: ~
+        0.1.0
+
+    Fields: ~
+        {name}  (string)
+
+
+Mode                                                                      *Mode*
+
+    Since: ~
+        0.10.0
+
+    Type: ~
+        \"n\"|\"v\"
+
+
+M.PI                                                                      *M.PI*
+    Pi
+
+    Since: ~
+        0.2.0
+
+    Type: ~
+        (number)
+
+
+==============================================================================
+Changelog by version                                          *my.mod.changelog*
+
+0.1.0 ~
+    |Config|
+
+0.2.0 ~
+    |M.echo|
+    |M.PI|
+
+0.10.0 ~
+    |Mode|
+
+"
+    );
+}